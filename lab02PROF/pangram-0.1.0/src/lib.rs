@@ -1 +1,2 @@
-pub mod pangram;
\ No newline at end of file
+pub mod pangram;
+pub mod wordgame;
\ No newline at end of file
@@ -1,7 +1,9 @@
 // we divived the business logic in functions in order to make tests easier
 // each performs a single task
 
-fn stats(text: &str) -> [u32; 26] {
+use crate::wordgame::WordIndex;
+
+pub fn stats(text: &str) -> [u32; 26] {
     let mut counts = [0; 26];
 
     // we must iterate over chars, not bytes   
@@ -51,12 +53,20 @@ fn is_pangram(counts: &[u32]) -> bool {
 pub fn run() {
     let args: Vec<String> = std::env::args().collect();
 
-    if args.len() != 2 {
-        eprintln!("Usage: {} <file>", args[0]);
-        std::process::exit(1);
+    // with a slice pattern we can match the number of args and the command at once
+    match args.as_slice() {
+        [_, file] => check_pangram(file),
+        [_, cmd, dict, letters] if is_word_game(cmd) => word_game(cmd, dict, letters),
+        _ => {
+            eprintln!("Usage: {} <file>", args[0]);
+            eprintln!("       {} rack|anagrams|cover <dictionary> <letters>", args[0]);
+            std::process::exit(1);
+        }
     }
+}
 
-    let contents = std::fs::read_to_string(&args[1]).expect("cannot read file");
+fn check_pangram(file: &str) {
+    let contents = std::fs::read_to_string(file).expect("cannot read file");
 
     let counts = stats(&contents);
 
@@ -71,6 +81,27 @@ pub fn run() {
     };
 }
 
+fn is_word_game(cmd: &str) -> bool {
+    matches!(cmd, "rack" | "anagrams" | "cover")
+}
+
+fn word_game(cmd: &str, dict: &str, letters: &str) {
+    let contents = std::fs::read_to_string(dict).expect("cannot read dictionary");
+
+    // the index is built once and then queried
+    let index = WordIndex::new(&contents);
+
+    let words = match cmd {
+        "rack" => index.formable(letters),
+        "anagrams" => index.anagrams(letters),
+        "cover" => index.covering(letters),
+        _ => unreachable!(),
+    };
+
+    for w in words {
+        println!("{}", w);
+    }
+}
 
 // please note, code has been splittend in simple functions in order to make tests easier
 #[cfg(test)] // this is a test module
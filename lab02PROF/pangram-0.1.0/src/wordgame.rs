@@ -0,0 +1,153 @@
+// word games built on top of the letter histograms computed by stats()
+// two words are anagrams if they have the same histogram, and a word can be
+// formed from a rack if the rack histogram "contains" the word histogram
+
+use std::collections::HashMap;
+
+use crate::pangram::stats;
+
+// chars accepted as blank tiles in a rack
+const BLANKS: [char; 2] = ['?', '_'];
+
+pub struct WordIndex {
+    // each word is stored together with its histogram, computed only once
+    words: Vec<(String, [u32; 26])>,
+    // words grouped by histogram: all the anagrams share the same key
+    // arrays implement Hash and Eq, so they can be used directly as keys
+    anagrams: HashMap<[u32; 26], Vec<usize>>,
+}
+
+impl WordIndex {
+    // build the index from the contents of a dictionary file, one word per line
+    // words with non alphabetic chars (e.g. "don't") are skipped
+    pub fn new(contents: &str) -> Self {
+        let mut words: Vec<String> = contents
+            .lines()
+            .map(|l| l.trim().to_ascii_lowercase())
+            .filter(|w| !w.is_empty() && w.chars().all(|c| c.is_ascii_alphabetic()))
+            .collect();
+        words.sort();
+        words.dedup();
+
+        let mut index = WordIndex {
+            words: Vec::with_capacity(words.len()),
+            anagrams: HashMap::new(),
+        };
+
+        for w in words {
+            let counts = stats(&w);
+            index.anagrams.entry(counts).or_default().push(index.words.len());
+            index.words.push((w, counts));
+        }
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    // all the words that can be formed with the letters of the rack
+    // each '?' or '_' in the rack is a blank and can replace any letter
+    pub fn formable(&self, rack: &str) -> Vec<&str> {
+        let counts = stats(rack);
+        let blanks = rack.chars().filter(|c| BLANKS.contains(c)).count() as u32;
+        let size = counts.iter().sum::<u32>() + blanks;
+
+        self.words
+            .iter()
+            // a word longer than the rack can't fit, no need to compare histograms
+            .filter(|(w, _)| w.len() as u32 <= size)
+            .filter(|(_, wc)| missing(wc, &counts) <= blanks)
+            .map(|(w, _)| w.as_str())
+            .collect()
+    }
+
+    // all the words with the same letters of word, word itself excluded
+    pub fn anagrams(&self, word: &str) -> Vec<&str> {
+        let word = word.to_ascii_lowercase();
+        match self.anagrams.get(&stats(&word)) {
+            Some(ids) => ids
+                .iter()
+                .map(|&i| self.words[i].0.as_str())
+                .filter(|w| *w != word)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // all the words containing the given letters (at least as many times as they
+    // appear in letters): useful to find which words complete a pangram
+    pub fn covering(&self, letters: &str) -> Vec<&str> {
+        let counts = stats(letters);
+        self.words
+            .iter()
+            .filter(|(_, wc)| missing(&counts, wc) == 0)
+            .map(|(w, _)| w.as_str())
+            .collect()
+    }
+}
+
+// how many letters of "needed" are not available in "available"
+fn missing(needed: &[u32; 26], available: &[u32; 26]) -> u32 {
+    needed
+        .iter()
+        .zip(available.iter())
+        .map(|(n, a)| n.saturating_sub(*a))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DICT: &str = "listen\nSilent\nenlist\ntinsel\ncat\nact\ntac\ndon't\n\nquiz\njazz\nlisten\n";
+
+    #[test]
+    fn test_index_skips_invalid_and_duplicated_words() {
+        let index = WordIndex::new(DICT);
+        assert_eq!(index.len(), 9);
+    }
+
+    #[test]
+    fn test_missing() {
+        let word = stats("jazz");
+        assert_eq!(missing(&word, &stats("jaz")), 1);
+        assert_eq!(missing(&word, &stats("zzaj")), 0);
+        assert_eq!(missing(&word, &stats("")), 4);
+    }
+
+    #[test]
+    fn test_anagrams() {
+        let index = WordIndex::new(DICT);
+        assert_eq!(index.anagrams("Listen"), vec!["enlist", "silent", "tinsel"]);
+        assert_eq!(index.anagrams("cat"), vec!["act", "tac"]);
+        assert!(index.anagrams("dog").is_empty());
+    }
+
+    #[test]
+    fn test_formable() {
+        let index = WordIndex::new(DICT);
+        assert_eq!(index.formable("tacx"), vec!["act", "cat", "tac"]);
+        assert!(index.formable("jaz").is_empty());
+    }
+
+    #[test]
+    fn test_formable_with_blanks() {
+        let index = WordIndex::new(DICT);
+        assert_eq!(index.formable("ja?"), Vec::<&str>::new());
+        assert_eq!(index.formable("ja??"), vec!["act", "cat", "jazz", "tac"]);
+        assert_eq!(index.formable("qu_z"), vec!["quiz"]);
+    }
+
+    #[test]
+    fn test_covering() {
+        let index = WordIndex::new(DICT);
+        assert_eq!(index.covering("zq"), vec!["quiz"]);
+        assert_eq!(index.covering("zz"), vec!["jazz"]);
+        assert_eq!(index.covering("").len(), index.len());
+    }
+}
@@ -0,0 +1,150 @@
+// a CircularBuffer shared by many threads, behind a Mutex: write() waits while it's
+// full and read() waits while it's empty
+// put it in an Arc to share it; any number of threads can write and read
+//
+// the try_ variants never wait and the _timeout ones wait at most the given time,
+// both fail with the same errors as CircularBuffer (FullBuffer or EmptyBuffer)
+// after close() every write fails with Closed, reads return what's left and then
+// fail with Closed too; the threads waiting are woken up
+//
+// write() waits only with OverflowPolicy::Block, the default here; with the other
+// policies it does what CircularBuffer::write does. to set a policy or an eviction
+// callback (called with the lock held) build the CircularBuffer and convert it:
+// BlockingCircularBuffer::from(CircularBuffer::with_policy(8, policy).on_evict(f))
+
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::{CircularBuffer, Error, OverflowPolicy, Stats};
+
+struct State<T> {
+    buf: CircularBuffer<T>,
+    closed: bool,
+}
+
+impl<T> State<T> {
+    // a write has to wait for a read
+    fn must_wait(&self) -> bool {
+        !self.closed
+            && self.buf.policy == OverflowPolicy::Block
+            && !self.buf.auto_grow
+            && self.buf.len == self.buf.capacity()
+    }
+}
+
+pub struct BlockingCircularBuffer<T> {
+    state: Mutex<State<T>>,
+    // signaled when an element is read, or on close
+    not_full: Condvar,
+    // signaled when an element is written, or on close
+    not_empty: Condvar,
+}
+
+impl<T> BlockingCircularBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self::from(CircularBuffer::with_policy(capacity, OverflowPolicy::Block))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    // write to the buffer once the wait is over
+    fn write_locked(&self, mut state: MutexGuard<'_, State<T>>, element: T) -> Result<(), Error> {
+        if state.closed {
+            return Err(Error::Closed);
+        }
+        state.buf.write(element)?;
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    fn read_locked(&self, mut state: MutexGuard<'_, State<T>>) -> Result<T, Error> {
+        match state.buf.read() {
+            Ok(element) => {
+                drop(state);
+                self.not_full.notify_one();
+                Ok(element)
+            }
+            Err(_) if state.closed => Err(Error::Closed),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn write(&self, element: T) -> Result<(), Error> {
+        let state = self
+            .not_full
+            .wait_while(self.lock(), |s| s.must_wait())
+            .unwrap();
+        self.write_locked(state, element)
+    }
+
+    pub fn read(&self) -> Result<T, Error> {
+        let state = self
+            .not_empty
+            .wait_while(self.lock(), |s| !s.closed && s.buf.is_empty())
+            .unwrap();
+        self.read_locked(state)
+    }
+
+    pub fn try_write(&self, element: T) -> Result<(), Error> {
+        self.write_locked(self.lock(), element)
+    }
+
+    pub fn try_read(&self) -> Result<T, Error> {
+        self.read_locked(self.lock())
+    }
+
+    pub fn write_timeout(&self, element: T, timeout: Duration) -> Result<(), Error> {
+        let (state, _) = self
+            .not_full
+            .wait_timeout_while(self.lock(), timeout, |s| s.must_wait())
+            .unwrap();
+        self.write_locked(state, element)
+    }
+
+    pub fn read_timeout(&self, timeout: Duration) -> Result<T, Error> {
+        let (state, _) = self
+            .not_empty
+            .wait_timeout_while(self.lock(), timeout, |s| !s.closed && s.buf.is_empty())
+            .unwrap();
+        self.read_locked(state)
+    }
+
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_full.notify_all();
+        self.not_empty.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock().buf.capacity()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.lock().buf.stats()
+    }
+}
+
+impl<T> From<CircularBuffer<T>> for BlockingCircularBuffer<T> {
+    fn from(buf: CircularBuffer<T>) -> Self {
+        BlockingCircularBuffer {
+            state: Mutex::new(State { buf, closed: false }),
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+        }
+    }
+}
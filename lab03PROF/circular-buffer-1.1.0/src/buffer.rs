@@ -0,0 +1,439 @@
+// the heap allocated CircularBuffer, with everything else which needs std: the
+// modules next to it (iter, bytes, traits...) reach into its fields, which are
+// pub(crate) for them
+
+use std::collections::TryReserveError;
+use std::mem::MaybeUninit;
+use std::ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds};
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::ptr;
+use std::slice;
+
+use crate::{Drain, Error, Iter, IterMut};
+
+pub struct CircularBuffer<T> {
+    // only the len slots starting from head (wrapping around) hold a value,
+    // the others are uninitialised: no need for T: Default to fill them
+    pub(crate) data: Vec<MaybeUninit<T>>,
+    pub(crate) tail: usize,
+    pub(crate) head: usize,
+    pub(crate) len: usize,
+    // write() makes room instead of failing with FullBuffer
+    pub(crate) auto_grow: bool,
+    // what write() does when full
+    pub(crate) policy: OverflowPolicy,
+    // gets the elements dropped by the policy or by overwrite(), not those dropped
+    // by resize(); Send + Sync so that the buffer stays Send and Sync
+    pub(crate) on_evict: Option<Box<dyn FnMut(T) + Send + Sync>>,
+    pub(crate) stats: Stats,
+}
+
+// what write() does when the buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OverflowPolicy {
+    // fail with FullBuffer
+    #[default]
+    Reject,
+    // drop the oldest element to make room, as overwrite() does
+    DropOldest,
+    // drop the element being written, the buffer is left as it is
+    DropNewest,
+    // BlockingCircularBuffer waits for a read; a CircularBuffer alone can't wait
+    // and fails as with Reject
+    Block,
+}
+
+// counters since the buffer was created: elements written (also by overwrite()),
+// dropped to make room or by the policy, and read with read()
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Stats {
+    pub writes: u64,
+    pub drops: u64,
+    pub reads: u64,
+}
+
+// which elements resize() keeps when the new capacity is smaller than len
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Keep {
+    Oldest,
+    Newest,
+}
+
+
+impl<T> CircularBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        let mut buf = Vec::with_capacity(capacity);
+        buf.resize_with(capacity, MaybeUninit::uninit);
+        Self::from_slots(buf)
+    }
+
+    // as new(), but an error instead of a panic when the capacity can't be
+    // allocated (e.g. it comes from untrusted input, as when deserializing)
+    pub fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut buf = Vec::new();
+        buf.try_reserve_exact(capacity)?;
+        buf.resize_with(capacity, MaybeUninit::uninit);
+        Ok(Self::from_slots(buf))
+    }
+
+    fn from_slots(data: Vec<MaybeUninit<T>>) -> Self {
+        CircularBuffer {
+            data,
+            tail: 0,
+            head: 0,
+            len: 0,
+            auto_grow: false,
+            policy: OverflowPolicy::Reject,
+            on_evict: None,
+            stats: Stats::default(),
+        }
+    }
+
+    pub fn with_policy(capacity: usize, policy: OverflowPolicy) -> Self {
+        let mut buf = Self::new(capacity);
+        buf.policy = policy;
+        buf
+    }
+
+    // call f with each element dropped because the buffer was full, e.g. to log it:
+    // CircularBuffer::with_policy(64, OverflowPolicy::DropOldest).on_evict(|x| ...)
+    // the elements dropped by resize() to fit the new capacity don't go to f
+    pub fn on_evict<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(T) + Send + Sync + 'static,
+    {
+        self.on_evict = Some(Box::new(f));
+        self
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    fn evict(&mut self, element: T) {
+        self.stats.drops += 1;
+        if let Some(f) = &mut self.on_evict {
+            f(element);
+        }
+    }
+
+    // a buffer which doubles its capacity when written to while full
+    pub fn growable(capacity: usize) -> Self {
+        let mut buf = Self::new(capacity);
+        buf.auto_grow = true;
+        buf
+    }
+
+    pub fn set_auto_grow(&mut self, auto_grow: bool) {
+        self.auto_grow = auto_grow;
+    }
+
+    pub fn write(&mut self, element: T) -> Result<(), Error> {
+        if self.len == self.data.len() && self.auto_grow {
+            self.realloc((2 * self.len).max(1));
+        }
+        if self.len == self.data.len() {
+            match self.policy {
+                OverflowPolicy::Reject | OverflowPolicy::Block => return Err(Error::FullBuffer),
+                OverflowPolicy::DropOldest if self.len > 0 => {
+                    let oldest = self.pop_front().unwrap();
+                    self.evict(oldest);
+                }
+                // DropNewest, or DropOldest with no room at all
+                _ => {
+                    self.evict(element);
+                    return Ok(());
+                }
+            }
+        }
+        self.push_back(element);
+        Ok(())
+    }
+
+    // there must be a free slot
+    fn push_back(&mut self, element: T) {
+        // the slot is free, nothing to drop
+        self.data[self.tail].write(element);
+        self.tail = self.wrap(self.tail + 1);
+        self.len += 1;
+        self.stats.writes += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            // SAFETY: head is a live slot; moving head forward marks it as free,
+            // so the value is never read (or dropped) twice
+            let element = unsafe { self.data[self.head].assume_init_read() };
+            self.head = self.wrap(self.head + 1);
+            self.len -= 1;
+            Some(element)
+        }
+    }
+
+    pub fn read(&mut self) -> Result<T, Error> {
+        let element = self.pop_front().ok_or(Error::EmptyBuffer)?;
+        self.stats.reads += 1;
+        Ok(element)
+    }
+
+    pub fn clear(&mut self) {
+        // dropping the elements, without counting them as reads
+        while self.pop_front().is_some() {}
+    }
+
+    // write even if full, whatever the policy: the oldest element is dropped
+    pub fn overwrite(&mut self, element: T) {
+        if self.len == self.data.len() && !self.auto_grow {
+            match self.pop_front() {
+                Some(oldest) => self.evict(oldest),
+                // with no room at all the element itself is dropped
+                None => return self.evict(element),
+            }
+        }
+        if self.len == self.data.len() {
+            self.realloc((2 * self.len).max(1));
+        }
+        self.push_back(element);
+    }
+
+    pub fn make_contiguous(&mut self) {
+
+        // if it's empty, we can just reset the pointers
+        if self.len == 0 {
+            self.head = 0;
+            self.tail = 0;
+        } else {
+            // otherwise we need to make it contiguos: just rotate the slots until head
+            // is zero (moving them one by one doesn't work when the buffer is full)
+            self.data.rotate_left(self.head);
+            self.head = 0;
+            self.tail = self.wrap(self.len);
+        }
+    }
+
+    fn real_index(&self, index: usize) -> usize {
+        if index >= self.len {
+            panic!("out of bounds");
+        }
+        self.wrap(self.head + index)
+    }
+
+    // the index of the slot after index, wrapping around: index + n for any n up to
+    // the capacity, without % (which would divide by zero with no slots)
+    pub(crate) fn wrap(&self, index: usize) -> usize {
+        if index >= self.data.len() {
+            index - self.data.len()
+        } else {
+            index
+        }
+    }
+
+    // remove the newest element, for iterators consumed from the back
+    pub(crate) fn read_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.tail = self.wrap(self.head + self.len);
+        // SAFETY: tail was the last live slot and now it's free
+        Some(unsafe { self.data[self.tail].assume_init_read() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    // move the elements, in order, to new slots: they start from 0
+    pub(crate) fn realloc(&mut self, capacity: usize) {
+        assert!(self.len <= capacity);
+        let mut data = Vec::with_capacity(capacity);
+        data.resize_with(capacity, MaybeUninit::uninit);
+
+        let (first, second) = self.as_slices();
+        let (first, second) = (first.len(), second.len());
+        let src = self.data.as_ptr();
+        let dst = data.as_mut_ptr();
+        // SAFETY: the live slots are moved to the new vector, the old one is dropped
+        // without touching them (MaybeUninit never drops its value)
+        unsafe {
+            ptr::copy_nonoverlapping(src.add(self.head), dst, first);
+            ptr::copy_nonoverlapping(src, dst.add(first), second);
+        }
+
+        self.data = data;
+        self.head = 0;
+        self.tail = self.wrap(self.len);
+    }
+
+    // make room for at least additional more elements: the capacity becomes exactly
+    // len + additional, if it was smaller (the capacity is when writes fail, better
+    // not to surprise)
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+        if needed > self.data.len() {
+            self.realloc(needed);
+        }
+    }
+
+    // change the capacity; if there are more elements than it, keep tells which ones
+    // stay, the others are dropped
+    pub fn resize(&mut self, capacity: usize, keep: Keep) {
+        if self.len > capacity {
+            let extra = self.len - capacity;
+            match keep {
+                Keep::Oldest => self.drain(capacity..),
+                Keep::Newest => self.drain(..extra),
+            };
+        }
+        if capacity != self.data.len() {
+            self.realloc(capacity);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        if self.len < self.data.len() {
+            self.realloc(self.len);
+        }
+    }
+
+    // the elements don't wrap around the end of data
+    fn is_contiguous(&self) -> bool {
+        self.head + self.len <= self.data.len()
+    }
+
+    // the elements in order, split in the part from head to the end of data and the
+    // part which wrapped around to the start (empty if contiguous)
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first = self.len.min(self.data.len() - self.head);
+        let ptr = self.data.as_ptr().cast::<T>();
+        // SAFETY: the len slots from head, wrapping around, are live
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(self.head), first),
+                slice::from_raw_parts(ptr, self.len - first),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let first = self.len.min(self.data.len() - self.head);
+        let ptr = self.data.as_mut_ptr().cast::<T>();
+        // SAFETY: as in as_slices, and the two slices don't overlap
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(self.head), first),
+                slice::from_raw_parts_mut(ptr, self.len - first),
+            )
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        Iter::new(first, second)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (first, second) = self.as_mut_slices();
+        IterMut::new(first, second)
+    }
+
+    // remove the elements in range (by position, 0 is the oldest) and return them
+    // in order; the others stay in the buffer
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len,
+        };
+        if start > end || end > self.len {
+            panic!("out of bounds");
+        }
+        Drain::new(self, start, end)
+    }
+}
+
+// only the live elements must be dropped, the free slots hold nothing
+impl<T> Drop for CircularBuffer<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// a dyn callback is never unwind safe, but it's only called through &mut self:
+// a shared reference can't see it half way through a panic
+impl<T: RefUnwindSafe> RefUnwindSafe for CircularBuffer<T> {}
+impl<T: UnwindSafe> UnwindSafe for CircularBuffer<T> {}
+
+impl<T> Index<usize> for CircularBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        // SAFETY: real_index only returns live slots
+        unsafe { self.data[self.real_index(index)].assume_init_ref() }
+    }
+}
+
+impl<T> IndexMut<usize> for CircularBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let idx = self.real_index(index);
+        // SAFETY: real_index only returns live slots
+        unsafe { self.data[idx].assume_init_mut() }
+    }
+}
+
+impl<T> Deref for CircularBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        if !self.is_contiguous() {
+            panic!("not contiguous!!!")
+        }
+        self.as_slices().0
+    }
+}
+
+pub trait TryDeref {
+    type Target: ?Sized;
+
+    fn try_deref(&self) -> Result<&Self::Target, String>;
+}
+
+impl<T> TryDeref for CircularBuffer<T> {
+    type Target = [T];
+
+    fn try_deref(&self) -> Result<&Self::Target, String> {
+        if !self.is_contiguous() {
+            return Err("not contiguous".to_string());
+        }
+        Ok(self.as_slices().0)
+    }
+}
+
+impl<T> DerefMut for CircularBuffer<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.make_contiguous();
+        if !self.is_contiguous() {
+            panic!("not contiguous!!!")
+        }
+        self.as_mut_slices().0
+    }
+}
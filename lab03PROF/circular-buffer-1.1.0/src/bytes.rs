@@ -0,0 +1,93 @@
+// bulk copies in and out of the buffer, and a byte stream on top of them:
+// CircularBuffer<u8> is io::Read, io::BufRead and io::Write
+// the elements are copied in at most two chunks, one on each side of the wrap point,
+// so they must be Copy (nothing to drop, nothing to clone)
+
+use std::io::{self, BufRead, Read, Write};
+use std::ptr;
+
+use crate::CircularBuffer;
+
+impl<T: Copy> CircularBuffer<T> {
+    // copy as many elements of src as there is room for (all of them with auto-grow);
+    // returns how many were written. the overflow policy doesn't apply: what
+    // doesn't fit is left to the caller, not dropped
+    pub fn write_slice(&mut self, src: &[T]) -> usize {
+        let free = self.data.len() - self.len;
+        if src.len() > free && self.auto_grow {
+            self.realloc((self.len + src.len()).max(2 * self.data.len()));
+        }
+        let n = src.len().min(self.data.len() - self.len);
+
+        // the free slots go from tail to the end, then from the start
+        let first = n.min(self.data.len() - self.tail);
+        let dst = self.data.as_mut_ptr().cast::<T>();
+        // SAFETY: the n slots from tail are free and inside data
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), dst.add(self.tail), first);
+            ptr::copy_nonoverlapping(src.as_ptr().add(first), dst, n - first);
+        }
+        self.tail = self.wrap(self.tail + n);
+        self.len += n;
+        self.stats.writes += n as u64;
+        n
+    }
+
+    // copy the oldest elements to dst without removing them; returns how many
+    pub fn peek_slice(&self, dst: &mut [T]) -> usize {
+        let (first, second) = self.as_slices();
+        let n = dst.len().min(self.len);
+        let from_first = n.min(first.len());
+        dst[..from_first].copy_from_slice(&first[..from_first]);
+        dst[from_first..n].copy_from_slice(&second[..n - from_first]);
+        n
+    }
+
+    // move the oldest elements to dst; returns how many
+    pub fn read_into(&mut self, dst: &mut [T]) -> usize {
+        let n = self.peek_slice(dst);
+        self.skip(n);
+        n
+    }
+
+    // read the n oldest elements, being Copy there's nothing to drop
+    fn skip(&mut self, n: usize) {
+        assert!(n <= self.len, "out of bounds");
+        self.head = self.wrap(self.head + n);
+        self.len -= n;
+        self.stats.reads += n as u64;
+    }
+}
+
+// an empty buffer reads 0 bytes, as at the end of a file
+impl Read for CircularBuffer<u8> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.read_into(buf))
+    }
+}
+
+impl BufRead for CircularBuffer<u8> {
+    // the bytes up to the wrap point, the rest comes after consume()
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.skip(amt);
+    }
+}
+
+// a full buffer fails with WouldBlock, as a non-blocking socket: read from it
+// and try again
+impl Write for CircularBuffer<u8> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.write_slice(buf) {
+            0 if !buf.is_empty() => Err(io::ErrorKind::WouldBlock.into()),
+            n => Ok(n),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
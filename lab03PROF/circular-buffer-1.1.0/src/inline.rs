@@ -0,0 +1,151 @@
+// the same circular buffer, with the capacity fixed at compile time and the slots
+// in an array inside the struct: no heap allocation, it can live on the stack or in
+// a static, and it works without std
+//
+//     let mut buffer: CircularBuffer<u16, 64> = CircularBuffer::new();
+
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+use crate::Error;
+
+pub struct CircularBuffer<T, const N: usize> {
+    // as in the heap buffer, only the len slots from head hold a value
+    data: [MaybeUninit<T>; N],
+    tail: usize,
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> CircularBuffer<T, N> {
+    // const, so it can initialise a static
+    pub const fn new() -> Self {
+        CircularBuffer {
+            data: [const { MaybeUninit::uninit() }; N],
+            tail: 0,
+            head: 0,
+            len: 0,
+        }
+    }
+
+    // index + n for n up to N, without % (N can be 0)
+    fn wrap(&self, index: usize) -> usize {
+        if index >= N {
+            index - N
+        } else {
+            index
+        }
+    }
+
+    pub fn write(&mut self, element: T) -> Result<(), Error> {
+        if self.len == N {
+            Err(Error::FullBuffer)
+        } else {
+            self.data[self.tail].write(element);
+            self.tail = self.wrap(self.tail + 1);
+            self.len += 1;
+            Ok(())
+        }
+    }
+
+    pub fn read(&mut self) -> Result<T, Error> {
+        if self.len == 0 {
+            Err(Error::EmptyBuffer)
+        } else {
+            // SAFETY: head is a live slot and moving head forward frees it
+            let element = unsafe { self.data[self.head].assume_init_read() };
+            self.head = self.wrap(self.head + 1);
+            self.len -= 1;
+            Ok(element)
+        }
+    }
+
+    pub fn clear(&mut self) {
+        while self.len > 0 {
+            self.read().unwrap();
+        }
+    }
+
+    pub fn overwrite(&mut self, element: T) {
+        if N == 0 {
+            return;
+        }
+        if self.len == N {
+            self.read().unwrap();
+        }
+        self.write(element).unwrap();
+    }
+
+    pub fn make_contiguous(&mut self) {
+        if self.len == 0 {
+            self.head = 0;
+            self.tail = 0;
+        } else {
+            self.data.rotate_left(self.head);
+            self.head = 0;
+            self.tail = self.wrap(self.len);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    // the elements in order, as in the heap buffer
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first = self.len.min(N - self.head);
+        let ptr = self.data.as_ptr().cast::<T>();
+        // SAFETY: the len slots from head, wrapping around, are live
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(self.head), first),
+                slice::from_raw_parts(ptr, self.len - first),
+            )
+        }
+    }
+
+    fn real_index(&self, index: usize) -> usize {
+        if index >= self.len {
+            panic!("out of bounds");
+        }
+        self.wrap(self.head + index)
+    }
+}
+
+impl<T, const N: usize> Default for CircularBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for CircularBuffer<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Index<usize> for CircularBuffer<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        // SAFETY: real_index only returns live slots
+        unsafe { self.data[self.real_index(index)].assume_init_ref() }
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for CircularBuffer<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let idx = self.real_index(index);
+        // SAFETY: real_index only returns live slots
+        unsafe { self.data[idx].assume_init_mut() }
+    }
+}
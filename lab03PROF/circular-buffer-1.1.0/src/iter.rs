@@ -0,0 +1,230 @@
+// iterators over the elements of a CircularBuffer, from the oldest to the newest
+// the elements are at most two slices (see as_slices), the borrowing iterators
+// simply walk the first one and then the second one
+
+use std::iter::FusedIterator;
+use std::slice;
+
+use crate::CircularBuffer;
+
+pub struct Iter<'a, T> {
+    first: slice::Iter<'a, T>,
+    second: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(first: &'a [T], second: &'a [T]) -> Self {
+        Iter {
+            first: first.iter(),
+            second: second.iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.second.next_back().or_else(|| self.first.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+pub struct IterMut<'a, T> {
+    first: slice::IterMut<'a, T>,
+    second: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(first: &'a mut [T], second: &'a mut [T]) -> Self {
+        IterMut {
+            first: first.iter_mut(),
+            second: second.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.second.next_back().or_else(|| self.first.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+// owns the buffer and reads the elements out of it
+pub struct IntoIter<T>(CircularBuffer<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.read().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.read_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for CircularBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CircularBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut CircularBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// the elements in start..end are read out one by one; when the Drain is dropped
+// the ones not returned are dropped and the elements after end are moved back to
+// close the gap
+// while it's alive the buffer only holds the elements before start: if the Drain
+// is leaked (mem::forget) the others are leaked too, but never dropped twice
+pub struct Drain<'a, T> {
+    buf: &'a mut CircularBuffer<T>,
+    start: usize,
+    // positions (from head) of the elements still to be returned
+    next: usize,
+    end: usize,
+    // the elements after the drained range
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<'a, T> Drain<'a, T> {
+    pub(crate) fn new(buf: &'a mut CircularBuffer<T>, start: usize, end: usize) -> Self {
+        let tail_len = buf.len - end;
+        buf.len = start;
+        buf.tail = buf.wrap(buf.head + start);
+        Drain {
+            buf,
+            start,
+            next: start,
+            end,
+            tail_start: end,
+            tail_len,
+        }
+    }
+
+    // SAFETY: pos must be in next..end and never taken twice
+    unsafe fn take(&mut self, pos: usize) -> T {
+        let slot = self.buf.wrap(self.buf.head + pos);
+        self.buf.data[slot].assume_init_read()
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.end {
+            return None;
+        }
+        self.next += 1;
+        // SAFETY: next - 1 was still to be returned
+        Some(unsafe { self.take(self.next - 1) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: end was still to be returned
+        Some(unsafe { self.take(self.end) })
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        // drop what's left of the range
+        for _ in self.by_ref() {}
+
+        let buf = &mut *self.buf;
+        if self.start == 0 {
+            // the range was at the front: just move head past it
+            buf.head = buf.wrap(buf.head + self.tail_start);
+        } else {
+            // move the elements after the range back, from the first one: each goes to
+            // a slot which is free, either drained or already moved
+            for k in 0..self.tail_len {
+                let from = buf.wrap(buf.head + self.tail_start + k);
+                let to = buf.wrap(buf.head + self.start + k);
+                // SAFETY: from is live and is left free
+                let element = unsafe { buf.data[from].assume_init_read() };
+                buf.data[to].write(element);
+            }
+        }
+        buf.len = self.start + self.tail_len;
+        buf.tail = buf.wrap(buf.head + buf.len);
+    }
+}
@@ -0,0 +1,472 @@
+// a circular buffer of fixed-size records kept in a memory-mapped file, so that
+// the last N records survive a crash or a restart: open() finds them again
+//
+// the file is two copies of the header followed by the slots of the records:
+//
+//     0    header A    (magic, record size, capacity, seq, head, tail, len,
+//     128  header B     sum of the records, checksum of the header)
+//     256  slot 0, slot 1, ... slot capacity - 1
+//
+// every change writes a new header, alternating between A and B, with a sequence
+// number one higher: if a crash tears the header being written, the other one
+// still describes the previous state. the sum of the live records (a hash of each
+// one, with its slot) detects records torn or overwritten after their header; on
+// open the newest header which matches both its checksum and the records wins
+//
+// operations are ordered so that the older header always stays valid too: records
+// go in free slots before the header which makes them live, and overwrite() first
+// drops the oldest record (one header) and then writes in its slot (another)
+//
+// the changes reach the page cache at once, which is enough if the process dies;
+// call flush() to have them on disk in case the machine goes down
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::marker::PhantomData;
+use std::mem;
+use std::path::Path;
+use std::ptr;
+
+use memmap2::MmapMut;
+
+use crate::Error;
+
+const MAGIC: u64 = u64::from_le_bytes(*b"CIRCBUF1");
+const HEADER_SLOT: usize = 128;
+const DATA_START: usize = 2 * HEADER_SLOT;
+
+/// A type which can be stored as raw bytes and read back.
+///
+/// # Safety
+///
+/// Implement it only for types without padding bytes, pointers or references, for
+/// which any bit pattern is a valid value: integers, floats, arrays and
+/// `#[repr(C)]` structs of them without padding.
+pub unsafe trait Record: Copy + 'static {}
+
+macro_rules! records {
+    ($($t:ty)*) => { $(unsafe impl Record for $t {})* };
+}
+records!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+unsafe impl<T: Record, const N: usize> Record for [T; N] {}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Header {
+    magic: u64,
+    record_size: u64,
+    capacity: u64,
+    seq: u64,
+    head: u64,
+    tail: u64,
+    len: u64,
+    data_sum: u64,
+    checksum: u64,
+}
+
+// 64 bit FNV-1a, good enough to notice torn writes
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, &b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+const FNV_START: u64 = 0xcbf29ce484222325;
+
+impl Header {
+    fn compute_checksum(&self) -> u64 {
+        let fields = [
+            self.magic,
+            self.record_size,
+            self.capacity,
+            self.seq,
+            self.head,
+            self.tail,
+            self.len,
+            self.data_sum,
+        ];
+        fields
+            .iter()
+            .fold(FNV_START, |h, f| fnv(h, &f.to_le_bytes()))
+    }
+}
+
+pub struct MmapCircularBuffer<T: Record> {
+    map: MmapMut,
+    // the last header written
+    header: Header,
+    torn: bool,
+    record: PhantomData<T>,
+}
+
+impl<T: Record> MmapCircularBuffer<T> {
+    // a new empty buffer, replacing path if it exists
+    pub fn create<P: AsRef<Path>>(path: P, capacity: usize) -> io::Result<Self> {
+        assert!(mem::size_of::<T>() > 0, "records can't be zero-sized");
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len((DATA_START + capacity * mem::size_of::<T>()) as u64)?;
+        // SAFETY: as for any mapped file, nobody else must change it while it's mapped
+        let map = unsafe { MmapMut::map_mut(&file)? };
+
+        let mut buf = MmapCircularBuffer {
+            map,
+            header: Header {
+                magic: MAGIC,
+                record_size: mem::size_of::<T>() as u64,
+                capacity: capacity as u64,
+                ..Header::default()
+            },
+            torn: false,
+            record: PhantomData,
+        };
+        // both headers valid from the start
+        buf.commit();
+        buf.commit();
+        Ok(buf)
+    }
+
+    // reopen a buffer written before; fails with InvalidData if the file isn't one
+    // or neither header matches the records
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        // SAFETY: as in create
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        let mut buf = MmapCircularBuffer {
+            map,
+            header: Header::default(),
+            torn: false,
+            record: PhantomData,
+        };
+        if buf.map.len() < DATA_START {
+            return Err(invalid("file too short"));
+        }
+
+        let mut headers = [buf.read_header(0), buf.read_header(1)];
+        headers.sort_by_key(|h| std::cmp::Reverse(h.seq));
+        let valid: Vec<bool> = headers.iter().map(|h| buf.is_consistent(h)).collect();
+        match valid.iter().position(|&v| v) {
+            Some(i) => {
+                buf.header = headers[i];
+                buf.torn = valid.contains(&false);
+                Ok(buf)
+            }
+            None => Err(invalid("no valid header, the file is corrupted")),
+        }
+    }
+
+    // open path if it exists, otherwise create it
+    pub fn open_or_create<P: AsRef<Path>>(path: P, capacity: usize) -> io::Result<Self> {
+        match File::open(path.as_ref()) {
+            Ok(_) => Self::open(path),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::create(path, capacity),
+            Err(e) => Err(e),
+        }
+    }
+
+    // true if open() found a header or a record torn by a crash: the buffer is back
+    // to the state before the write which was interrupted
+    pub fn torn_write_detected(&self) -> bool {
+        self.torn
+    }
+
+    fn read_header(&self, slot: usize) -> Header {
+        // SAFETY: the map is at least DATA_START bytes and any bytes are a Header
+        unsafe { ptr::read_unaligned(self.map.as_ptr().add(slot * HEADER_SLOT).cast()) }
+    }
+
+    // the header is intact, describes a buffer of T in this file and its records are
+    // the ones in the slots
+    fn is_consistent(&self, h: &Header) -> bool {
+        let size = mem::size_of::<T>() as u64;
+        let ok = h.magic == MAGIC
+            && h.checksum == h.compute_checksum()
+            && h.record_size == size
+            && h.capacity.checked_mul(size) == Some((self.map.len() - DATA_START) as u64)
+            && h.len <= h.capacity
+            && h.head < h.capacity.max(1)
+            && h.tail == (h.head + h.len) % h.capacity.max(1);
+        if !ok {
+            return false;
+        }
+        let capacity = h.capacity as usize;
+        let sum = (0..h.len as usize)
+            .map(|i| self.record_hash((h.head as usize + i) % capacity))
+            .fold(0u64, u64::wrapping_add);
+        sum == h.data_sum
+    }
+
+    // write the header in memory to the slot after the last one written
+    fn commit(&mut self) {
+        self.header.seq += 1;
+        self.header.checksum = self.header.compute_checksum();
+        let offset = (self.header.seq % 2) as usize * HEADER_SLOT;
+        // SAFETY: the header fits in its slot
+        unsafe { ptr::write_unaligned(self.map.as_mut_ptr().add(offset).cast(), self.header) };
+    }
+
+    fn slot_range(&self, slot: usize) -> std::ops::Range<usize> {
+        let start = DATA_START + slot * mem::size_of::<T>();
+        start..start + mem::size_of::<T>()
+    }
+
+    fn record_hash(&self, slot: usize) -> u64 {
+        fnv(
+            fnv(FNV_START, &(slot as u64).to_le_bytes()),
+            &self.map[self.slot_range(slot)],
+        )
+    }
+
+    fn get_slot(&self, slot: usize) -> T {
+        // SAFETY: the slot is inside the map and any bytes are a valid Record
+        unsafe { ptr::read_unaligned(self.map[self.slot_range(slot)].as_ptr().cast()) }
+    }
+
+    fn wrap(&self, slot: usize) -> usize {
+        if slot >= self.capacity() {
+            slot - self.capacity()
+        } else {
+            slot
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.header.capacity as usize
+    }
+
+    pub fn len(&self) -> usize {
+        self.header.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn write(&mut self, record: T) -> Result<(), Error> {
+        if self.len() == self.capacity() {
+            return Err(Error::FullBuffer);
+        }
+        let tail = self.header.tail as usize;
+        let range = self.slot_range(tail);
+        // SAFETY: the slot is inside the map, Record has no padding
+        unsafe { ptr::write_unaligned(self.map[range].as_mut_ptr().cast(), record) };
+
+        self.header.data_sum = self.header.data_sum.wrapping_add(self.record_hash(tail));
+        self.header.tail = self.wrap(tail + 1) as u64;
+        self.header.len += 1;
+        self.commit();
+        Ok(())
+    }
+
+    pub fn read(&mut self) -> Result<T, Error> {
+        if self.is_empty() {
+            return Err(Error::EmptyBuffer);
+        }
+        let head = self.header.head as usize;
+        let record = self.get_slot(head);
+
+        self.header.data_sum = self.header.data_sum.wrapping_sub(self.record_hash(head));
+        self.header.head = self.wrap(head + 1) as u64;
+        self.header.len -= 1;
+        self.commit();
+        Ok(record)
+    }
+
+    // as CircularBuffer::overwrite, the oldest record makes room if it's full
+    pub fn overwrite(&mut self, record: T) {
+        if self.capacity() == 0 {
+            return;
+        }
+        if self.len() == self.capacity() {
+            self.read().unwrap();
+        }
+        self.write(record).unwrap();
+    }
+
+    pub fn clear(&mut self) {
+        self.header.head = self.header.tail;
+        self.header.len = 0;
+        self.header.data_sum = 0;
+        self.commit();
+    }
+
+    // the index-th record from the oldest
+    pub fn get(&self, index: usize) -> Option<T> {
+        (index < self.len()).then(|| self.get_slot(self.wrap(self.header.head as usize + index)))
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = T> + ExactSizeIterator + '_ {
+        (0..self.len()).map(|i| self.get(i).unwrap())
+    }
+
+    // wait until the changes are on disk
+    pub fn flush(&self) -> io::Result<()> {
+        self.map.flush()
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // a file in the temp dir, removed when dropped
+    struct TestFile(PathBuf);
+
+    impl TestFile {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("circbuf-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_file(&path);
+            TestFile(path)
+        }
+
+        // change the byte at offset, as a crash in the middle of a write would
+        fn corrupt(&self, offset: usize) {
+            let mut bytes = std::fs::read(&self.0).unwrap();
+            bytes[offset] ^= 0xff;
+            std::fs::write(&self.0, bytes).unwrap();
+        }
+    }
+
+    impl Drop for TestFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    fn contents(buf: &MmapCircularBuffer<u64>) -> Vec<u64> {
+        buf.iter().collect()
+    }
+
+    #[test]
+    fn write_read_overwrite() {
+        let file = TestFile::new("ops");
+        let mut buf = MmapCircularBuffer::create(&file.0, 2).unwrap();
+        assert_eq!(Err(Error::EmptyBuffer), buf.read());
+        buf.write(1u64).unwrap();
+        buf.write(2).unwrap();
+        assert_eq!(Err(Error::FullBuffer), buf.write(3));
+        buf.overwrite(3);
+        assert_eq!(contents(&buf), vec![2, 3]);
+        assert_eq!(Ok(2), buf.read());
+        buf.clear();
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn reopen_after_restart() {
+        let file = TestFile::new("reopen");
+        {
+            let mut buf = MmapCircularBuffer::create(&file.0, 3).unwrap();
+            for i in 0..5u64 {
+                buf.overwrite(i * 10);
+            }
+            buf.flush().unwrap();
+        }
+        let mut buf = MmapCircularBuffer::<u64>::open(&file.0).unwrap();
+        assert!(!buf.torn_write_detected());
+        assert_eq!(contents(&buf), vec![20, 30, 40]);
+        buf.overwrite(50);
+        drop(buf);
+
+        let buf = MmapCircularBuffer::<u64>::open_or_create(&file.0, 100).unwrap();
+        assert_eq!(buf.capacity(), 3);
+        assert_eq!(contents(&buf), vec![30, 40, 50]);
+    }
+
+    #[test]
+    fn open_rejects_other_files() {
+        let file = TestFile::new("other");
+        std::fs::write(&file.0, "not a ring buffer").unwrap();
+        assert_eq!(
+            MmapCircularBuffer::<u64>::open(&file.0)
+                .err()
+                .unwrap()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        // records of a different size
+        MmapCircularBuffer::<u32>::create(&file.0, 4).unwrap();
+        assert!(MmapCircularBuffer::<u64>::open(&file.0).is_err());
+        assert!(MmapCircularBuffer::<[u8; 4]>::open(&file.0).is_ok());
+    }
+
+    #[test]
+    fn torn_header_rolls_back_the_last_write() {
+        let file = TestFile::new("header");
+        let seq = {
+            let mut buf = MmapCircularBuffer::create(&file.0, 4).unwrap();
+            buf.write(1u64).unwrap();
+            buf.write(2).unwrap();
+            buf.header.seq
+        };
+        // the header of the last write is torn
+        file.corrupt((seq % 2) as usize * HEADER_SLOT + 40);
+
+        let buf = MmapCircularBuffer::<u64>::open(&file.0).unwrap();
+        assert!(buf.torn_write_detected());
+        assert_eq!(contents(&buf), vec![1]);
+    }
+
+    #[test]
+    fn torn_record_is_detected() {
+        let file = TestFile::new("record");
+        {
+            let mut buf = MmapCircularBuffer::create(&file.0, 2).unwrap();
+            buf.write(1u64).unwrap();
+            buf.write(2).unwrap();
+            // a crash in overwrite(): the oldest record was dropped and the new one
+            // is half written
+            buf.read().unwrap();
+        }
+        file.corrupt(DATA_START + 3);
+
+        let buf = MmapCircularBuffer::<u64>::open(&file.0).unwrap();
+        assert!(buf.torn_write_detected());
+        assert_eq!(contents(&buf), vec![2]);
+
+        // a live record changed under both headers
+        file.corrupt(DATA_START + 8);
+        assert!(MmapCircularBuffer::<u64>::open(&file.0).is_err());
+    }
+
+    #[test]
+    fn struct_records() {
+        #[repr(C)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Sample {
+            time: u64,
+            value: f64,
+        }
+        unsafe impl Record for Sample {}
+
+        let file = TestFile::new("struct");
+        let mut buf = MmapCircularBuffer::create(&file.0, 8).unwrap();
+        buf.write(Sample {
+            time: 1,
+            value: 0.5,
+        })
+        .unwrap();
+        drop(buf);
+        let buf = MmapCircularBuffer::<Sample>::open(&file.0).unwrap();
+        assert_eq!(
+            buf.get(0),
+            Some(Sample {
+                time: 1,
+                value: 0.5
+            })
+        );
+        assert_eq!(buf.get(1), None);
+    }
+}
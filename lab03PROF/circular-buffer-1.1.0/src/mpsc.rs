@@ -0,0 +1,361 @@
+// an async bounded channel: any number of Senders, one Receiver, the elements wait
+// in a CircularBuffer in between
+//
+//     let (tx, mut rx) = mpsc::channel(64);
+//     tx.send(sample).await?;
+//     let sample = rx.recv().await?;
+//
+// send() waits while the buffer is full and recv() while it's empty, by leaving
+// their waker in the shared state for the other side to wake: no timers, no
+// threads, nothing tied to a runtime, so it works with any executor
+// Sender is also a futures Sink and Receiver a Stream
+//
+// when the last Sender is dropped (or closed as a Sink) recv() returns what's left
+// and then fails with Closed (the Stream ends); when the Receiver is dropped, or
+// closed, every send fails with Closed and the element is dropped
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{ready, Context, Poll, Waker};
+
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use crate::{CircularBuffer, Error};
+
+struct State<T> {
+    buf: CircularBuffer<T>,
+    senders: usize,
+    // the Receiver was dropped or closed
+    closed: bool,
+    // the slots promised by Sink::poll_ready to the next start_send
+    reserved: usize,
+    // the tasks waiting for room, and the one waiting for an element
+    send_wakers: Vec<Waker>,
+    recv_waker: Option<Waker>,
+}
+
+impl<T> State<T> {
+    fn has_room(&self) -> bool {
+        self.buf.len() + self.reserved < self.buf.capacity()
+    }
+
+    fn wake_senders(&mut self) {
+        for waker in self.send_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
+    fn wake_receiver(&mut self) {
+        if let Some(waker) = self.recv_waker.take() {
+            waker.wake();
+        }
+    }
+
+    // what Receiver::try_recv returns
+    fn read(&mut self) -> Result<T, Error> {
+        match self.buf.read() {
+            Ok(element) => {
+                self.wake_senders();
+                Ok(element)
+            }
+            Err(_) if self.senders == 0 || self.closed => Err(Error::Closed),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Ok when there's room, otherwise cx is woken later
+    fn poll_room(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        if self.closed {
+            return Poll::Ready(Err(Error::Closed));
+        }
+        if !self.has_room() {
+            // a future polled again before being woken is already in the list
+            if !self.send_wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                self.send_wakers.push(cx.waker().clone());
+            }
+            return Poll::Pending;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    // write the element in slot when there's room, otherwise cx is woken later;
+    // the room is checked and taken under the same lock, so another sender can't
+    // steal it in between
+    fn poll_write(&self, cx: &mut Context<'_>, slot: &mut Option<T>) -> Poll<Result<(), Error>> {
+        let mut state = self.lock();
+        if let Err(e) = ready!(state.poll_room(cx)) {
+            *slot = None;
+            return Poll::Ready(Err(e));
+        }
+        state.buf.write(slot.take().unwrap()).unwrap();
+        state.wake_receiver();
+        Poll::Ready(Ok(()))
+    }
+}
+
+// capacity 0 can't be: nothing could ever be sent
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "capacity must be at least 1");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buf: CircularBuffer::new(capacity),
+            senders: 1,
+            closed: false,
+            reserved: 0,
+            send_wakers: Vec::new(),
+            recv_waker: None,
+        }),
+    });
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+            reserved: false,
+            closed: false,
+        },
+        Receiver { shared },
+    )
+}
+
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+    // this Sender holds a slot reserved by Sink::poll_ready
+    reserved: bool,
+    // closed with Sink::poll_close: it doesn't count as a sender any more
+    closed: bool,
+}
+
+impl<T> Sender<T> {
+    // wait for room, then write element
+    pub fn send(&self, element: T) -> Send<'_, T> {
+        Send {
+            shared: &self.shared,
+            // a closed Sender fails at the first poll
+            element: Some(element).filter(|_| !self.closed),
+        }
+    }
+
+    // write element if there's room now
+    pub fn try_send(&self, element: T) -> Result<(), Error> {
+        let mut state = self.shared.lock();
+        if state.closed || self.closed {
+            return Err(Error::Closed);
+        }
+        if !state.has_room() {
+            return Err(Error::FullBuffer);
+        }
+        state.buf.write(element).unwrap();
+        state.wake_receiver();
+        Ok(())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed || self.shared.lock().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.lock().buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.lock().buf.capacity()
+    }
+
+    // give back the slot reserved by poll_ready, if any, and stop counting as a
+    // sender: the receiver must find out when there's nothing more to wait for
+    fn close_sender(&mut self) {
+        let mut state = self.shared.lock();
+        if self.reserved {
+            self.reserved = false;
+            state.reserved -= 1;
+            state.wake_senders();
+        }
+        if !self.closed {
+            self.closed = true;
+            state.senders -= 1;
+            if state.senders == 0 {
+                state.wake_receiver();
+            }
+        }
+    }
+}
+
+// a clone of a closed Sender is closed too
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        if !self.closed {
+            self.shared.lock().senders += 1;
+        }
+        Sender {
+            shared: Arc::clone(&self.shared),
+            reserved: false,
+            closed: self.closed,
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.close_sender();
+    }
+}
+
+// the future returned by Sender::send: it borrows the shared state, not the Sender,
+// so it's Send whenever T is
+pub struct Send<'a, T> {
+    shared: &'a Shared<T>,
+    // None once written, or if the Sender was closed
+    element: Option<T>,
+}
+
+impl<T> Unpin for Send<'_, T> {}
+
+impl<T> Future for Send<'_, T> {
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if this.element.is_none() {
+            return Poll::Ready(Err(Error::Closed));
+        }
+        this.shared.poll_write(cx, &mut this.element)
+    }
+}
+
+// poll_ready() reserves a slot, so start_send() always finds room even with other
+// senders around and never has to keep the element: flushing has nothing to do,
+// all is in the buffer already
+impl<T> Sink<T> for Sender<T> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+        if this.closed {
+            return Poll::Ready(Err(Error::Closed));
+        }
+        if this.reserved {
+            return Poll::Ready(Ok(()));
+        }
+        let mut state = this.shared.lock();
+        ready!(state.poll_room(cx))?;
+        state.reserved += 1;
+        this.reserved = true;
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, element: T) -> Result<(), Error> {
+        let this = self.get_mut();
+        assert!(this.reserved, "start_send without poll_ready");
+        this.reserved = false;
+        let mut state = this.shared.lock();
+        state.reserved -= 1;
+        if state.closed {
+            return Err(Error::Closed);
+        }
+        state.buf.write(element).unwrap();
+        state.wake_receiver();
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // this Sender is done: when it's the last one the receiver gets what's left,
+    // then Closed
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.get_mut().close_sender();
+        Poll::Ready(Ok(()))
+    }
+}
+
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    // wait for an element; fails with Closed once the senders are gone and the
+    // buffer is empty
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+
+    // an element if there's one now: EmptyBuffer if not, Closed if there won't be
+    pub fn try_recv(&mut self) -> Result<T, Error> {
+        self.shared.lock().read()
+    }
+
+    // the read, the closed check and the waker are under the same lock: a sender
+    // writing or dropping after it finds the waker and wakes it
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, Error>> {
+        let mut state = self.shared.lock();
+        match state.read() {
+            Err(Error::EmptyBuffer) => {
+                state.recv_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            result => Poll::Ready(result),
+        }
+    }
+
+    // no more sends, the elements already sent can still be received
+    pub fn close(&mut self) {
+        let mut state = self.shared.lock();
+        state.closed = true;
+        state.wake_senders();
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.lock().buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.lock().buf.capacity()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+// the future returned by Receiver::recv
+pub struct Recv<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for Recv<'_, T> {
+    type Output = Result<T, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.poll_recv(cx).map(Result::ok)
+    }
+}
@@ -0,0 +1,288 @@
+// a ring buffer shared by two threads without locks: one thread writes through the
+// Producer, the other reads through the Consumer
+//
+//     let (mut tx, mut rx) = spsc::channel(1024);
+//     thread::spawn(move || tx.write(sample));
+//     let sample = rx.read();
+//
+// head and tail count positions modulo 2 * capacity, so that a full buffer
+// (tail - head == capacity) is different from an empty one (tail == head)
+// tail is written only by the producer; head by the consumer and, to drop the
+// oldest element in overwrite(), by the producer, so it's updated with compare and
+// swap. its lowest bit is set by the consumer while it copies elements out: the
+// producer can't take those, overwrite() waits for the copy to end
+// (the only case in which one side waits for the other)
+
+use std::cell::UnsafeCell;
+use std::hint;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::Error;
+
+const BUSY: usize = 1;
+
+struct Shared<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    // position << 1 | BUSY
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+// the slots are accessed by one side at a time, as head and tail tell
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    // position + n, modulo 2 * capacity
+    fn advance(&self, pos: usize, n: usize) -> usize {
+        let pos = pos + n;
+        if pos >= 2 * self.capacity() {
+            pos - 2 * self.capacity()
+        } else {
+            pos
+        }
+    }
+
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * self.capacity() - head
+        }
+    }
+
+    fn slot(&self, pos: usize) -> *mut T {
+        let index = if pos >= self.capacity() { pos - self.capacity() } else { pos };
+        assert!(index < self.capacity());
+        // a pointer from the whole slice, valid for the slots after it too
+        unsafe { UnsafeCell::raw_get(self.slots.as_ptr().add(index)).cast() }
+    }
+
+    // head and tail are loaded one after the other: if both move in between the
+    // distance can come out more than the capacity, which no buffer ever holds
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire) >> 1;
+        self.distance(head, self.tail.load(Ordering::Acquire)).min(self.capacity())
+    }
+
+    // n positions starting from from, as at most two runs of slots: (start, len)
+    fn runs(&self, from: usize, n: usize) -> [(usize, usize); 2] {
+        let index = if from >= self.capacity() { from - self.capacity() } else { from };
+        let first = n.min(self.capacity() - index);
+        [(index, first), (0, n - first)]
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        // both halves are gone: drop the elements still inside
+        let mut head = *self.head.get_mut() >> 1;
+        let tail = *self.tail.get_mut();
+        while head != tail {
+            unsafe { ptr::drop_in_place(self.slot(head)) };
+            head = self.advance(head, 1);
+        }
+    }
+}
+
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    // positions up to 2 * capacity, shifted left by one
+    assert!(capacity <= usize::MAX / 4, "capacity too large");
+    let mut slots = Vec::with_capacity(capacity);
+    slots.resize_with(capacity, || UnsafeCell::new(MaybeUninit::uninit()));
+    let shared = Arc::new(Shared {
+        slots: slots.into_boxed_slice(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+    });
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+impl<T> Producer<T> {
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    // the elements not read yet; the consumer may read more at any moment
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    // store element in the free slot at tail and publish it
+    fn push(&mut self, tail: usize, element: T) {
+        let s = &self.shared;
+        unsafe { s.slot(tail).write(element) };
+        s.tail.store(s.advance(tail, 1), Ordering::Release);
+    }
+
+    pub fn write(&mut self, element: T) -> Result<(), Error> {
+        let s = &self.shared;
+        let tail = s.tail.load(Ordering::Relaxed);
+        // acquire: the consumer is done with the slots it released
+        let head = s.head.load(Ordering::Acquire) >> 1;
+        if s.distance(head, tail) == s.capacity() {
+            return Err(Error::FullBuffer);
+        }
+        self.push(tail, element);
+        Ok(())
+    }
+
+    // like CircularBuffer::overwrite: if the buffer is full the oldest element is
+    // dropped to make room
+    pub fn overwrite(&mut self, element: T) {
+        let s = &self.shared;
+        if s.capacity() == 0 {
+            return;
+        }
+        let tail = s.tail.load(Ordering::Relaxed);
+        let mut spins = 0;
+        loop {
+            let head = s.head.load(Ordering::Acquire);
+            if s.distance(head >> 1, tail) < s.capacity() {
+                break;
+            }
+            if head & BUSY != 0 {
+                // the consumer is reading the oldest element, soon there will be room;
+                // if it takes long it may have been preempted, let it run
+                spins += 1;
+                if spins < 100 {
+                    hint::spin_loop();
+                } else {
+                    thread::yield_now();
+                }
+                continue;
+            }
+            // take the oldest element away from the consumer
+            let next = s.advance(head >> 1, 1) << 1;
+            if s.head.compare_exchange(head, next, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+                unsafe { ptr::drop_in_place(s.slot(head >> 1)) };
+                break;
+            }
+        }
+        self.push(tail, element);
+    }
+
+    // copy as many elements as there is room for, at most two memcpy; returns how
+    // many were written
+    pub fn write_slice(&mut self, elements: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let s = &self.shared;
+        let tail = s.tail.load(Ordering::Relaxed);
+        let head = s.head.load(Ordering::Acquire) >> 1;
+        let n = elements.len().min(s.capacity() - s.distance(head, tail));
+        if n == 0 {
+            return 0;
+        }
+
+        let mut src = elements.as_ptr();
+        for (start, len) in s.runs(tail, n) {
+            unsafe {
+                ptr::copy_nonoverlapping(src, s.slot(start), len);
+                src = src.add(len);
+            }
+        }
+        s.tail.store(s.advance(tail, n), Ordering::Release);
+        n
+    }
+}
+
+impl<T> Consumer<T> {
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    // the elements available; the producer may write more at any moment
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // mark up to max elements as being read: returns their first position and how
+    // many they are; the producer can't take them until release()
+    fn claim(&mut self, max: usize) -> (usize, usize) {
+        let s = &self.shared;
+        loop {
+            let head = s.head.load(Ordering::Acquire);
+            // acquire: the elements up to tail are written
+            let n = s.distance(head >> 1, s.tail.load(Ordering::Acquire)).min(max);
+            if n == 0 {
+                return (head >> 1, 0);
+            }
+            // fails only if the producer has just dropped the oldest element
+            if s.head.compare_exchange(head, head | BUSY, Ordering::AcqRel, Ordering::Acquire).is_ok() {
+                return (head >> 1, n);
+            }
+        }
+    }
+
+    // free the n claimed slots starting at head
+    fn release(&mut self, head: usize, n: usize) {
+        let s = &self.shared;
+        s.head.store(s.advance(head, n) << 1, Ordering::Release);
+    }
+
+    pub fn read(&mut self) -> Result<T, Error> {
+        let (head, n) = self.claim(1);
+        if n == 0 {
+            return Err(Error::EmptyBuffer);
+        }
+        let element = unsafe { self.shared.slot(head).read() };
+        self.release(head, 1);
+        Ok(element)
+    }
+
+    // copy as many elements as are available into buf, at most two memcpy; returns
+    // how many were read
+    pub fn read_slice(&mut self, buf: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let (head, n) = self.claim(buf.len());
+        if n == 0 {
+            return 0;
+        }
+        let mut dst = buf.as_mut_ptr();
+        for (start, len) in self.shared.runs(head, n) {
+            unsafe {
+                ptr::copy_nonoverlapping(self.shared.slot(start), dst, len);
+                dst = dst.add(len);
+            }
+        }
+        self.release(head, n);
+        n
+    }
+}
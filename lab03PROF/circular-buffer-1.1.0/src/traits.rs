@@ -0,0 +1,140 @@
+// the standard traits, all about the elements in order from the oldest: where they
+// sit in data (the head offset) doesn't matter, two buffers holding 1, 2, 3 are
+// equal, hash the same and print the same, whatever their past. the capacity
+// doesn't matter either, as for Vec
+//
+// with the serde feature a buffer is serialized as its capacity and its elements,
+// { "capacity": 4, "elements": [1, 2, 3] }, and comes back with the same ones
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{CircularBuffer, OverflowPolicy};
+
+impl<T: fmt::Debug> fmt::Debug for CircularBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// the same capacity, elements, policy, auto-grow and stats; the eviction callback
+// can't be cloned, the clone has none
+impl<T: Clone> Clone for CircularBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut buf = CircularBuffer::with_policy(self.capacity(), self.policy);
+        for element in self.iter() {
+            buf.write(element.clone()).unwrap();
+        }
+        buf.auto_grow = self.auto_grow;
+        buf.stats = self.stats;
+        buf
+    }
+}
+
+impl<T: PartialEq> PartialEq for CircularBuffer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for CircularBuffer<T> {}
+
+impl<T: Hash> Hash for CircularBuffer<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // the length first, as for slices, so that [[1], [2]] and [[1, 2]] differ
+        state.write_usize(self.len());
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+// each element is written as by write(), except that there's no error to return:
+// when full with Reject (or Block) it's written as by overwrite(), the oldest
+// element makes room, as in a ring; the other policies and auto-grow work as usual
+impl<T> Extend<T> for CircularBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            match self.policy {
+                OverflowPolicy::Reject | OverflowPolicy::Block => self.overwrite(element),
+                _ => self.write(element).expect("only Reject and Block fail when full"),
+            }
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Extend<&'a T> for CircularBuffer<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+// a buffer just big enough for the elements
+impl<T> FromIterator<T> for CircularBuffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let elements: Vec<T> = iter.into_iter().collect();
+        let mut buf = CircularBuffer::new(elements.len());
+        buf.extend(elements);
+        buf
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::ser::{Serialize, Serializer};
+
+    use crate::CircularBuffer;
+
+    // what goes in the serialized form, the same fields both ways
+    #[derive(serde::Serialize)]
+    #[serde(rename = "CircularBuffer")]
+    struct Ser<'a, T> {
+        capacity: usize,
+        elements: Elements<'a, T>,
+    }
+
+    struct Elements<'a, T>(&'a CircularBuffer<T>);
+
+    impl<T: Serialize> Serialize for Elements<'_, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.0.iter())
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(rename = "CircularBuffer")]
+    struct De<T> {
+        capacity: usize,
+        elements: Vec<T>,
+    }
+
+    impl<T: Serialize> Serialize for CircularBuffer<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Ser {
+                capacity: self.capacity(),
+                elements: Elements(self),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for CircularBuffer<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let De { capacity, elements } = De::deserialize(deserializer)?;
+            if elements.len() > capacity {
+                return Err(de::Error::custom(format_args!(
+                    "{} elements in a buffer of capacity {}",
+                    elements.len(),
+                    capacity
+                )));
+            }
+            // the capacity comes from the input: a wrong one must be an error
+            let mut buf = CircularBuffer::try_new(capacity).map_err(|e| {
+                de::Error::custom(format_args!("capacity {}: {}", capacity, e))
+            })?;
+            buf.extend(elements);
+            Ok(buf)
+        }
+    }
+}
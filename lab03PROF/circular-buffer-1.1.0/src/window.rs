@@ -0,0 +1,167 @@
+// the last N samples of a signal with their statistics, kept up to date on every
+// push instead of looping over the window each time
+//
+// sum and mean/variance (Welford's method, adding the new sample and removing the
+// evicted one) are O(1); min and max use monotonic deques, O(1) amortised: the
+// min deque holds the samples which can still become the minimum, increasing from
+// the front, so the minimum is the front one (the same, decreasing, for the max)
+// percentiles need sorting a copy of the window, so they're computed on demand
+//
+// NaN samples give meaningless statistics
+
+use crate::CircularBuffer;
+
+pub struct RollingWindow {
+    samples: CircularBuffer<f64>,
+    // how many samples were pushed, the deques hold (number, sample)
+    pushed: u64,
+    sum: f64,
+    mean: f64,
+    // sum of the squared differences from the mean
+    m2: f64,
+    min: CircularBuffer<(u64, f64)>,
+    max: CircularBuffer<(u64, f64)>,
+}
+
+impl RollingWindow {
+    pub fn new(size: usize) -> Self {
+        RollingWindow {
+            samples: CircularBuffer::new(size),
+            pushed: 0,
+            sum: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            min: CircularBuffer::new(size),
+            max: CircularBuffer::new(size),
+        }
+    }
+
+    // add a sample; when the window is full the oldest one is evicted and returned
+    pub fn push(&mut self, x: f64) -> Option<f64> {
+        if self.samples.capacity() == 0 {
+            return Some(x);
+        }
+        let evicted = if self.samples.len() == self.samples.capacity() {
+            let old = self.samples.read().unwrap();
+            self.remove_stats(old);
+            Some(old)
+        } else {
+            None
+        };
+        self.samples.write(x).unwrap();
+        self.add_stats(x);
+
+        // the samples which left the window leave the deques too
+        let first = (self.pushed + 1).saturating_sub(self.samples.capacity() as u64);
+        for deque in [&mut self.min, &mut self.max] {
+            if deque.iter().next().is_some_and(|&(n, _)| n < first) {
+                deque.read().unwrap();
+            }
+        }
+        // x is newer than the samples bigger (smaller) than it: they can't be the
+        // minimum (maximum) any more
+        push_monotonic(&mut self.min, (self.pushed, x), |back| back >= x);
+        push_monotonic(&mut self.max, (self.pushed, x), |back| back <= x);
+
+        self.pushed += 1;
+        evicted
+    }
+
+    fn add_stats(&mut self, x: f64) {
+        let n = self.samples.len() as f64;
+        self.sum += x;
+        let d = x - self.mean;
+        self.mean += d / n;
+        self.m2 += d * (x - self.mean);
+    }
+
+    fn remove_stats(&mut self, x: f64) {
+        let n = self.samples.len() as f64;
+        self.sum -= x;
+        if n == 0.0 {
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            return;
+        }
+        let d = x - self.mean;
+        self.mean -= d / n;
+        self.m2 -= d * (x - self.mean);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.samples.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        *self = RollingWindow::new(self.capacity());
+    }
+
+    // from the oldest to the newest
+    pub fn samples(&self) -> crate::Iter<'_, f64> {
+        self.samples.iter()
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then_some(self.mean)
+    }
+
+    // population variance, of the samples in the window
+    pub fn variance(&self) -> Option<f64> {
+        // rounding errors may push it a bit below 0
+        (!self.is_empty()).then(|| (self.m2 / self.len() as f64).max(0.0))
+    }
+
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.min.iter().next().map(|&(_, x)| x)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.max.iter().next().map(|&(_, x)| x)
+    }
+
+    // p between 0 and 100, interpolating linearly between the two closest samples
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        assert!((0.0..=100.0).contains(&p), "percentile out of range");
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        let rank = p / 100.0 * (sorted.len() - 1) as f64;
+        let below = rank.floor() as usize;
+        let above = rank.ceil() as usize;
+        Some(sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64))
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+}
+
+// remove from the back the entries for which discard is true, then push entry
+fn push_monotonic<F>(deque: &mut CircularBuffer<(u64, f64)>, entry: (u64, f64), discard: F)
+where
+    F: Fn(f64) -> bool,
+{
+    while deque.iter().next_back().is_some_and(|&(_, back)| discard(back)) {
+        deque.read_back();
+    }
+    deque.write(entry).unwrap();
+}
@@ -0,0 +1,113 @@
+use circular_buffer::{BlockingCircularBuffer, Error};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn try_variants_do_not_wait() {
+    let buffer = BlockingCircularBuffer::new(1);
+    assert_eq!(Err(Error::EmptyBuffer), buffer.try_read());
+    assert!(buffer.try_write('1').is_ok());
+    assert_eq!(Err(Error::FullBuffer), buffer.try_write('2'));
+    assert_eq!(Ok('1'), buffer.try_read());
+}
+
+#[test]
+fn timeouts() {
+    let buffer = BlockingCircularBuffer::new(1);
+    let start = Instant::now();
+    assert_eq!(Err(Error::EmptyBuffer), buffer.read_timeout(Duration::from_millis(20)));
+    buffer.write(1).unwrap();
+    assert_eq!(Err(Error::FullBuffer), buffer.write_timeout(2, Duration::from_millis(20)));
+    assert!(start.elapsed() >= Duration::from_millis(40));
+    assert_eq!(Ok(1), buffer.read_timeout(Duration::from_millis(20)));
+}
+
+#[test]
+fn write_waits_for_a_read() {
+    let buffer = Arc::new(BlockingCircularBuffer::new(1));
+    buffer.write(1).unwrap();
+    let reader = {
+        let buffer = Arc::clone(&buffer);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            buffer.read()
+        })
+    };
+    // blocks until the reader makes room
+    buffer.write(2).unwrap();
+    assert_eq!(reader.join().unwrap(), Ok(1));
+    assert_eq!(buffer.read(), Ok(2));
+}
+
+#[test]
+fn close_wakes_the_waiting_threads() {
+    let empty = Arc::new(BlockingCircularBuffer::<i32>::new(1));
+    let full = Arc::new(BlockingCircularBuffer::new(1));
+    full.write(0).unwrap();
+
+    let mut waiting = Vec::new();
+    for _ in 0..3 {
+        let empty = Arc::clone(&empty);
+        waiting.push(thread::spawn(move || empty.read().map(|_| ())));
+        let full = Arc::clone(&full);
+        waiting.push(thread::spawn(move || full.write(1)));
+    }
+    thread::sleep(Duration::from_millis(20));
+    empty.close();
+    full.close();
+    for t in waiting {
+        assert_eq!(t.join().unwrap(), Err(Error::Closed));
+    }
+}
+
+#[test]
+fn reads_after_close_drain_the_buffer() {
+    let buffer = BlockingCircularBuffer::new(2);
+    buffer.write('a').unwrap();
+    buffer.close();
+    assert!(buffer.is_closed());
+    assert_eq!(Err(Error::Closed), buffer.try_write('b'));
+    assert_eq!(Ok('a'), buffer.read());
+    assert_eq!(Err(Error::Closed), buffer.read());
+    assert_eq!(Err(Error::Closed), buffer.try_read());
+}
+
+// many producers and consumers: every element is read exactly once
+#[test]
+fn multiple_producers_and_consumers() {
+    const PRODUCERS: u64 = 4;
+    const N: u64 = 10_000;
+    let buffer = Arc::new(BlockingCircularBuffer::new(16));
+
+    let producers: Vec<_> = (0..PRODUCERS)
+        .map(|p| {
+            let buffer = Arc::clone(&buffer);
+            thread::spawn(move || {
+                for i in 0..N {
+                    buffer.write(p * N + i).unwrap();
+                }
+            })
+        })
+        .collect();
+    let consumers: Vec<_> = (0..3)
+        .map(|_| {
+            let buffer = Arc::clone(&buffer);
+            thread::spawn(move || {
+                let mut got = Vec::new();
+                while let Ok(x) = buffer.read() {
+                    got.push(x);
+                }
+                got
+            })
+        })
+        .collect();
+
+    for p in producers {
+        p.join().unwrap();
+    }
+    buffer.close();
+    let mut all: Vec<u64> = consumers.into_iter().flat_map(|c| c.join().unwrap()).collect();
+    all.sort();
+    assert!(all.into_iter().eq(0..PRODUCERS * N));
+}
@@ -0,0 +1,92 @@
+use circular_buffer::CircularBuffer;
+use std::io::{BufRead, ErrorKind, Read, Write};
+
+// capacity 8 with head and tail in the middle: the next writes wrap around
+fn offset_buffer() -> CircularBuffer<u8> {
+    let mut buffer = CircularBuffer::new(8);
+    buffer.write_slice(b"xxxxx");
+    let mut skip = [0; 5];
+    buffer.read_into(&mut skip);
+    buffer
+}
+
+#[test]
+fn write_slice_across_the_wrap_point() {
+    let mut buffer = offset_buffer();
+    assert_eq!(buffer.write_slice(b"hello world"), 8);
+    assert_eq!(buffer.as_slices(), (&b"hel"[..], &b"lo wo"[..]));
+    assert_eq!(buffer.write_slice(b"!"), 0);
+}
+
+#[test]
+fn read_into_and_peek_slice() {
+    let mut buffer = offset_buffer();
+    buffer.write_slice(b"abcdef");
+
+    let mut header = [0; 4];
+    assert_eq!(buffer.peek_slice(&mut header), 4);
+    assert_eq!(&header, b"abcd");
+    assert_eq!(buffer.len(), 6);
+
+    let mut out = [0; 10];
+    assert_eq!(buffer.read_into(&mut out), 6);
+    assert_eq!(&out[..6], b"abcdef");
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.read_into(&mut out), 0);
+}
+
+#[test]
+fn write_slice_with_auto_grow() {
+    let mut buffer = CircularBuffer::growable(4);
+    buffer.write_slice(&[1, 2, 3]);
+    buffer.read().unwrap();
+    assert_eq!(buffer.write_slice(&[4, 5, 6, 7, 8, 9]), 6);
+    assert_eq!(buffer.capacity(), 8);
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn slices_of_other_copy_types() {
+    let mut buffer = CircularBuffer::new(3);
+    buffer.write(0.5).unwrap();
+    assert_eq!(buffer.write_slice(&[1.5, 2.5, 3.5]), 2);
+    let mut out = [0.0; 3];
+    assert_eq!(buffer.read_into(&mut out), 3);
+    assert_eq!(out, [0.5, 1.5, 2.5]);
+}
+
+#[test]
+fn io_read_and_write() {
+    let mut buffer = offset_buffer();
+    buffer.write_all(b"ping").unwrap();
+    write!(buffer, "{}", 42).unwrap();
+    buffer.flush().unwrap();
+
+    let err = buffer.write_all(b"overflow").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+
+    let mut s = String::new();
+    buffer.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "ping42ov");
+    // the inherent read() hides the one of io::Read
+    assert_eq!(Read::read(&mut buffer, &mut [0; 4]).unwrap(), 0);
+}
+
+#[test]
+fn buf_read_lines() {
+    let mut buffer = offset_buffer();
+    buffer.write_all(b"ab\ncd\ne").unwrap();
+    assert_eq!(buffer.fill_buf().unwrap(), b"ab\n");
+    let lines: Vec<String> = buffer.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, vec!["ab", "cd", "e"]);
+}
+
+#[test]
+fn buf_read_consume() {
+    let mut buffer = offset_buffer();
+    buffer.write_all(b"12345").unwrap();
+    buffer.consume(4);
+    assert_eq!(buffer.fill_buf().unwrap(), b"5");
+    buffer.consume(1);
+    assert!(buffer.fill_buf().unwrap().is_empty());
+}
@@ -163,3 +163,59 @@ fn string_buffer() {
     assert_eq!(0, buffer.read().unwrap().len());
     assert_eq!(Ok("Testing".to_string()), buffer.read());
 }
+
+// a type without Default
+#[derive(Debug, PartialEq)]
+struct NoDefault(u32);
+
+#[test]
+fn buffer_of_types_without_default() {
+    let mut buffer = CircularBuffer::new(2);
+    assert!(buffer.write(NoDefault(1)).is_ok());
+    assert!(buffer.write(NoDefault(2)).is_ok());
+    buffer.overwrite(NoDefault(3));
+    assert_eq!(buffer[0], NoDefault(2));
+    buffer[1] = NoDefault(4);
+    assert_eq!(Ok(NoDefault(2)), buffer.read());
+    assert_eq!(Ok(NoDefault(4)), buffer.read());
+    assert_eq!(Err(Error::EmptyBuffer), buffer.read());
+}
+
+#[test]
+fn drop_frees_only_the_live_elements() {
+    let element = Rc::new(());
+    {
+        let mut buffer = CircularBuffer::new(3);
+        for _ in 0..3 {
+            buffer.write(Rc::clone(&element)).unwrap();
+        }
+        drop(buffer.read().unwrap());
+        buffer.overwrite(Rc::clone(&element));
+        buffer.overwrite(Rc::clone(&element));
+        assert_eq!(Rc::strong_count(&element), 4);
+    }
+    assert_eq!(Rc::strong_count(&element), 1);
+}
+
+#[test]
+fn overwrite_drops_the_oldest_item() {
+    let old = Rc::new(());
+    let mut buffer = CircularBuffer::new(1);
+    buffer.write(Rc::clone(&old)).unwrap();
+    buffer.overwrite(Rc::new(()));
+    assert_eq!(Rc::strong_count(&old), 1);
+}
+
+#[test]
+fn deref_after_make_contiguous() {
+    let mut buffer = CircularBuffer::new(3);
+    for s in ["a", "b", "c"] {
+        buffer.write(s.to_string()).unwrap();
+    }
+    buffer.read().unwrap();
+    buffer.write("d".to_string()).unwrap();
+    buffer.make_contiguous();
+    assert_eq!(&*buffer, &["b", "c", "d"]);
+    buffer[0].push('!');
+    assert_eq!(buffer.read(), Ok("b!".to_string()));
+}
@@ -0,0 +1,88 @@
+use circular_buffer::inline::CircularBuffer;
+use circular_buffer::Error;
+use std::rc::Rc;
+
+// no heap needed, not even to build it
+static EMPTY: CircularBuffer<u8, 4> = CircularBuffer::new();
+
+#[test]
+fn static_buffer() {
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.capacity(), 4);
+}
+
+#[test]
+fn write_read_overwrite() {
+    let mut buffer = CircularBuffer::<char, 2>::new();
+    assert_eq!(Err(Error::EmptyBuffer), buffer.read());
+    assert!(buffer.write('1').is_ok());
+    assert!(buffer.write('2').is_ok());
+    assert_eq!(Err(Error::FullBuffer), buffer.write('3'));
+    buffer.overwrite('A');
+    assert_eq!(Ok('2'), buffer.read());
+    assert_eq!(Ok('A'), buffer.read());
+    assert_eq!(Err(Error::EmptyBuffer), buffer.read());
+}
+
+#[test]
+fn read_position_is_maintained_across_the_wrap_point() {
+    let mut buffer = CircularBuffer::<i32, 3>::new();
+    buffer.write(1).unwrap();
+    buffer.write(2).unwrap();
+    assert_eq!(Ok(1), buffer.read());
+    buffer.write(3).unwrap();
+    buffer.write(4).unwrap();
+    assert_eq!(buffer.as_slices(), (&[2, 3][..], &[4][..]));
+    assert_eq!(buffer[0], 2);
+    buffer[2] = 40;
+    assert_eq!(buffer[2], 40);
+}
+
+#[test]
+fn make_contiguous() {
+    let mut buffer = CircularBuffer::<String, 3>::new();
+    for s in ["a", "b", "c"] {
+        buffer.write(s.to_string()).unwrap();
+    }
+    buffer.read().unwrap();
+    buffer.write("d".to_string()).unwrap();
+    buffer.make_contiguous();
+    assert_eq!(buffer.as_slices().0, &["b", "c", "d"]);
+    buffer.clear();
+    buffer.make_contiguous();
+    assert!(buffer.as_slices().0.is_empty());
+}
+
+#[test]
+fn zero_capacity() {
+    let mut buffer = CircularBuffer::<i32, 0>::default();
+    assert_eq!(Err(Error::FullBuffer), buffer.write(1));
+    buffer.overwrite(1);
+    assert_eq!(Err(Error::EmptyBuffer), buffer.read());
+    buffer.make_contiguous();
+}
+
+#[test]
+fn clear_and_drop_free_the_elements() {
+    let element = Rc::new(());
+    let mut buffer = CircularBuffer::<_, 3>::new();
+    buffer.write(Rc::clone(&element)).unwrap();
+    buffer.clear();
+    assert_eq!(Rc::strong_count(&element), 1);
+    {
+        let mut buffer = CircularBuffer::<_, 3>::new();
+        for _ in 0..5 {
+            buffer.overwrite(Rc::clone(&element));
+        }
+        assert_eq!(Rc::strong_count(&element), 4);
+    }
+    assert_eq!(Rc::strong_count(&element), 1);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn index_out_of_bounds() {
+    let mut buffer = CircularBuffer::<i32, 3>::new();
+    buffer.write(1).unwrap();
+    let _ = buffer[1];
+}
@@ -0,0 +1,151 @@
+use circular_buffer::CircularBuffer;
+use std::rc::Rc;
+
+// a buffer of capacity 4 holding 1, 2, 3, 4 with the data wrapped around:
+// the slots are [3, 4, 1, 2]
+fn wrapped() -> CircularBuffer<i32> {
+    let mut buffer = CircularBuffer::new(4);
+    for i in [-1, 0, 1, 2] {
+        buffer.write(i).unwrap();
+    }
+    buffer.read().unwrap();
+    buffer.read().unwrap();
+    buffer.write(3).unwrap();
+    buffer.write(4).unwrap();
+    buffer
+}
+
+#[test]
+fn as_slices_of_wrapped_buffer() {
+    let mut buffer = wrapped();
+    assert_eq!(buffer.as_slices(), (&[1, 2][..], &[3, 4][..]));
+    let (first, second) = buffer.as_mut_slices();
+    first[0] = 10;
+    second[1] = 40;
+    assert_eq!(buffer.as_slices(), (&[10, 2][..], &[3, 40][..]));
+}
+
+#[test]
+fn as_slices_of_contiguous_and_empty_buffers() {
+    let mut buffer = CircularBuffer::new(3);
+    assert_eq!(buffer.as_slices(), (&[][..], &[][..]));
+    buffer.write('a').unwrap();
+    buffer.write('b').unwrap();
+    assert_eq!(buffer.as_slices(), (&['a', 'b'][..], &[][..]));
+
+    let empty = CircularBuffer::<char>::new(0);
+    assert_eq!(empty.as_slices(), (&[][..], &[][..]));
+    assert_eq!(empty.iter().next(), None);
+}
+
+#[test]
+fn iter_in_both_directions() {
+    let buffer = wrapped();
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(buffer.iter().rev().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+
+    let mut it = buffer.iter();
+    assert_eq!(it.len(), 4);
+    assert_eq!(it.next(), Some(&1));
+    assert_eq!(it.next_back(), Some(&4));
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.collect::<Vec<_>>(), vec![&2, &3]);
+}
+
+#[test]
+fn iter_mut_and_for_loops() {
+    let mut buffer = wrapped();
+    for x in buffer.iter_mut() {
+        *x *= 10;
+    }
+    for x in &mut buffer {
+        *x += 1;
+    }
+    let mut sum = 0;
+    for x in &buffer {
+        sum += x;
+    }
+    assert_eq!(sum, 104);
+    assert_eq!(buffer.len(), 4);
+}
+
+#[test]
+fn into_iter_owned() {
+    let buffer = wrapped();
+    let mut it = buffer.into_iter();
+    assert_eq!(it.len(), 4);
+    assert_eq!(it.next_back(), Some(4));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.collect::<Vec<_>>(), vec![2, 3]);
+}
+
+#[test]
+fn into_iter_drops_the_elements_left() {
+    let element = Rc::new(());
+    let mut buffer = CircularBuffer::new(3);
+    for _ in 0..3 {
+        buffer.write(Rc::clone(&element)).unwrap();
+    }
+    let mut it = buffer.into_iter();
+    it.next();
+    drop(it);
+    assert_eq!(Rc::strong_count(&element), 1);
+}
+
+#[test]
+fn drain_all() {
+    let mut buffer = wrapped();
+    assert_eq!(buffer.drain(..).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert!(buffer.is_empty());
+    buffer.write(5).unwrap();
+    assert_eq!(buffer.read(), Ok(5));
+}
+
+#[test]
+fn drain_front_and_back() {
+    let mut buffer = wrapped();
+    assert_eq!(buffer.drain(..3).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&4]);
+
+    let mut buffer = wrapped();
+    assert_eq!(buffer.drain(2..).rev().collect::<Vec<_>>(), vec![4, 3]);
+    assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    buffer.write(5).unwrap();
+    buffer.write(6).unwrap();
+    assert_eq!(buffer.into_iter().collect::<Vec<_>>(), vec![1, 2, 5, 6]);
+}
+
+#[test]
+fn drain_middle_closes_the_gap() {
+    let mut buffer = wrapped();
+    let mut drain = buffer.drain(1..=2);
+    assert_eq!(drain.len(), 2);
+    assert_eq!(drain.next(), Some(2));
+    drop(drain);
+    assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&1, &4]);
+    buffer.write(7).unwrap();
+    buffer.write(8).unwrap();
+    assert_eq!(buffer.write(9), Err(circular_buffer::Error::FullBuffer));
+    assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&1, &4, &7, &8]);
+}
+
+#[test]
+fn drain_drops_the_elements_not_returned() {
+    let element = Rc::new(());
+    let mut buffer = CircularBuffer::new(4);
+    for _ in 0..4 {
+        buffer.write(Rc::clone(&element)).unwrap();
+    }
+    buffer.drain(1..3);
+    assert_eq!(Rc::strong_count(&element), 3);
+    assert_eq!(buffer.len(), 2);
+    drop(buffer);
+    assert_eq!(Rc::strong_count(&element), 1);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn drain_out_of_bounds() {
+    let mut buffer = wrapped();
+    buffer.drain(2..5);
+}
@@ -0,0 +1,197 @@
+use circular_buffer::{mpsc, Error};
+use futures::executor::block_on;
+use futures::task::{waker, ArcWake};
+use futures::{join, FutureExt, SinkExt, StreamExt};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+// remembers being woken
+struct Flag(AtomicBool);
+
+impl ArcWake for Flag {
+    fn wake_by_ref(flag: &Arc<Self>) {
+        flag.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn send_and_recv() {
+    let (tx, mut rx) = mpsc::channel(2);
+    block_on(async {
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert_eq!(Err(Error::FullBuffer), tx.try_send(3));
+        assert_eq!(Ok(1), rx.recv().await);
+        assert_eq!(Ok(2), rx.recv().await);
+        assert_eq!(Err(Error::EmptyBuffer), rx.try_recv());
+    });
+}
+
+#[test]
+fn send_waits_for_room() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let producer = async {
+        for i in 0..10 {
+            tx.send(i).await.unwrap();
+        }
+        drop(tx);
+    };
+    let consumer = async {
+        let mut received = Vec::new();
+        while let Ok(i) = rx.recv().await {
+            received.push(i);
+        }
+        received
+    };
+    let ((), received) = block_on(async { join!(producer, consumer) });
+    assert_eq!(received, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn closes_when_all_senders_drop() {
+    let (tx, mut rx) = mpsc::channel(4);
+    let tx2 = tx.clone();
+    tx.try_send('a').unwrap();
+    drop(tx);
+    tx2.try_send('b').unwrap();
+    drop(tx2);
+    // what was sent is still received
+    assert_eq!(Ok('a'), rx.try_recv());
+    assert_eq!(Ok('b'), block_on(rx.recv()));
+    assert_eq!(Err(Error::Closed), block_on(rx.recv()));
+}
+
+#[test]
+fn parked_receiver_wakes_when_the_last_sender_drops() {
+    let (tx, mut rx) = mpsc::channel::<i32>(1);
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = waker(Arc::clone(&flag));
+    let mut cx = Context::from_waker(&waker);
+
+    let mut recv = rx.recv();
+    assert!(recv.poll_unpin(&mut cx).is_pending());
+    drop(tx);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert_eq!(Poll::Ready(Err(Error::Closed)), recv.poll_unpin(&mut cx));
+
+    // the same with the sender dropped by another thread while recv() waits
+    let (tx, mut rx) = mpsc::channel::<i32>(1);
+    let dropper = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        drop(tx);
+    });
+    assert_eq!(Err(Error::Closed), block_on(rx.recv()));
+    dropper.join().unwrap();
+}
+
+fn assert_send<F: Send>(_: &F) {}
+
+#[test]
+fn send_future_needs_only_send_elements() {
+    let (tx, _rx) = mpsc::channel(1);
+    let send = tx.send(Cell::new(1));
+    assert_send(&send);
+    block_on(send).unwrap();
+}
+
+#[test]
+fn closing_the_sink_ends_the_stream() {
+    let (mut tx, rx) = mpsc::channel(3);
+    let tx2 = tx.clone();
+    drop(tx2);
+    block_on(async {
+        tx.send_all(&mut futures::stream::iter([Ok(1), Ok(2), Ok(3)])).await.unwrap();
+        SinkExt::close(&mut tx).await.unwrap();
+    });
+    // the receiver isn't left waiting, even if tx is still around
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![1, 2, 3]);
+    assert!(tx.is_closed());
+    assert_eq!(Err(Error::Closed), tx.try_send(4));
+    assert_eq!(Err(Error::Closed), block_on(tx.send(4)));
+}
+
+#[test]
+fn dropped_sink_gives_back_its_slot() {
+    let (mut tx, mut rx) = mpsc::channel(1);
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = waker(Arc::clone(&flag));
+    let mut cx = Context::from_waker(&waker);
+
+    // poll_ready keeps the only slot for tx
+    assert_eq!(Poll::Ready(Ok(())), tx.poll_ready_unpin(&mut cx));
+    let other = tx.clone();
+    assert_eq!(Err(Error::FullBuffer), other.try_send(1));
+    let mut send = other.send(2);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    // dropped without start_send: nothing is lost, the slot goes to the next sender
+    drop(tx);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert_eq!(Poll::Ready(Ok(())), send.poll_unpin(&mut cx));
+    assert_eq!(Ok(2), rx.try_recv());
+}
+
+#[test]
+fn send_fails_when_receiver_is_gone() {
+    let (tx, mut rx) = mpsc::channel(1);
+    tx.try_send(1).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    assert_eq!(Err(Error::Closed), block_on(tx.send(2)));
+    assert_eq!(Ok(1), rx.try_recv());
+    assert_eq!(Err(Error::Closed), rx.try_recv());
+
+    let (tx, rx) = mpsc::channel(1);
+    tx.try_send(1).unwrap();
+    // a send waiting for room is woken up
+    let waiting = thread::spawn(move || block_on(tx.send(2)));
+    drop(rx);
+    assert_eq!(Err(Error::Closed), waiting.join().unwrap());
+}
+
+#[test]
+fn stream_and_sink() {
+    let (mut tx, rx) = mpsc::channel(3);
+    let producer = async move {
+        let mut items = futures::stream::iter((0..20).map(Ok));
+        tx.send_all(&mut items).await.unwrap();
+    };
+    let consumer = rx.map(|i| i * 2).collect::<Vec<i32>>();
+    let ((), doubled) = block_on(async { join!(producer, consumer) });
+    assert_eq!(doubled, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn many_senders_on_threads() {
+    let (tx, rx) = mpsc::channel(4);
+    let senders: Vec<_> = (0..4)
+        .map(|t| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                block_on(async {
+                    for i in 0..250 {
+                        tx.send(t * 1000 + i).await.unwrap();
+                    }
+                })
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let mut received: Vec<i32> = block_on(rx.collect());
+    for sender in senders {
+        sender.join().unwrap();
+    }
+    assert_eq!(received.len(), 1000);
+    // each sender's elements arrive in order
+    for t in 0..4 {
+        let mine: Vec<_> = received.iter().filter(|&&x| x / 1000 == t).collect();
+        assert!(mine.windows(2).all(|w| w[0] < w[1]));
+    }
+    received.sort();
+    received.dedup();
+    assert_eq!(received.len(), 1000);
+}
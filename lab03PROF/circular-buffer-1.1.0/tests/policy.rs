@@ -0,0 +1,148 @@
+use circular_buffer::{BlockingCircularBuffer, CircularBuffer, Error, OverflowPolicy, Stats};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// a buffer of capacity 2 with the given policy, recording what it evicts
+fn buffer(policy: OverflowPolicy) -> (CircularBuffer<i32>, Arc<Mutex<Vec<i32>>>) {
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&evicted);
+    let buffer = CircularBuffer::with_policy(2, policy).on_evict(move |x| log.lock().unwrap().push(x));
+    (buffer, evicted)
+}
+
+fn contents(buffer: &CircularBuffer<i32>) -> Vec<i32> {
+    buffer.iter().copied().collect()
+}
+
+#[test]
+fn reject_is_the_default() {
+    let mut buffer = CircularBuffer::new(1);
+    assert_eq!(buffer.policy(), OverflowPolicy::Reject);
+    buffer.write(1).unwrap();
+    assert_eq!(Err(Error::FullBuffer), buffer.write(2));
+}
+
+#[test]
+fn reject() {
+    let (mut buffer, evicted) = buffer(OverflowPolicy::Reject);
+    for i in 1..=3 {
+        let _ = buffer.write(i);
+    }
+    assert_eq!(contents(&buffer), vec![1, 2]);
+    assert!(evicted.lock().unwrap().is_empty());
+    assert_eq!(buffer.stats(), Stats { writes: 2, drops: 0, reads: 0 });
+}
+
+#[test]
+fn drop_oldest() {
+    let (mut buffer, evicted) = buffer(OverflowPolicy::DropOldest);
+    for i in 1..=4 {
+        assert!(buffer.write(i).is_ok());
+    }
+    assert_eq!(contents(&buffer), vec![3, 4]);
+    assert_eq!(*evicted.lock().unwrap(), vec![1, 2]);
+    assert_eq!(buffer.stats(), Stats { writes: 4, drops: 2, reads: 0 });
+}
+
+#[test]
+fn drop_newest() {
+    let (mut buffer, evicted) = buffer(OverflowPolicy::DropNewest);
+    for i in 1..=4 {
+        assert!(buffer.write(i).is_ok());
+    }
+    assert_eq!(contents(&buffer), vec![1, 2]);
+    assert_eq!(*evicted.lock().unwrap(), vec![3, 4]);
+    assert_eq!(buffer.stats(), Stats { writes: 2, drops: 2, reads: 0 });
+}
+
+#[test]
+fn block_cannot_wait_without_threads() {
+    let (mut buffer, evicted) = buffer(OverflowPolicy::Block);
+    buffer.write(1).unwrap();
+    buffer.write(2).unwrap();
+    assert_eq!(Err(Error::FullBuffer), buffer.write(3));
+    assert!(evicted.lock().unwrap().is_empty());
+}
+
+#[test]
+fn overwrite_reports_evictions() {
+    let (mut buffer, evicted) = buffer(OverflowPolicy::Reject);
+    for i in 1..=3 {
+        buffer.overwrite(i);
+    }
+    assert_eq!(*evicted.lock().unwrap(), vec![1]);
+
+    let (mut empty, evicted) = {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&evicted);
+        (CircularBuffer::new(0).on_evict(move |x| log.lock().unwrap().push(x)), evicted)
+    };
+    empty.overwrite(7);
+    assert_eq!(*evicted.lock().unwrap(), vec![7]);
+}
+
+#[test]
+fn counters() {
+    let (mut buffer, _) = buffer(OverflowPolicy::DropOldest);
+    for i in 0..5 {
+        buffer.write(i).unwrap();
+    }
+    buffer.read().unwrap();
+    buffer.read().unwrap();
+    assert_eq!(Err(Error::EmptyBuffer), buffer.read());
+    buffer.write(5).unwrap();
+    // clearing is neither a read nor a drop
+    buffer.clear();
+    assert_eq!(buffer.stats(), Stats { writes: 6, drops: 3, reads: 2 });
+}
+
+#[test]
+fn blocking_buffer_with_other_policies() {
+    let buffer = BlockingCircularBuffer::from(CircularBuffer::with_policy(1, OverflowPolicy::DropOldest));
+    buffer.write(1).unwrap();
+    // doesn't wait
+    buffer.write(2).unwrap();
+    assert_eq!(Ok(2), buffer.read());
+    assert_eq!(buffer.stats(), Stats { writes: 2, drops: 1, reads: 1 });
+
+    let buffer = BlockingCircularBuffer::from(CircularBuffer::with_policy(1, OverflowPolicy::Reject));
+    buffer.write(1).unwrap();
+    assert_eq!(Err(Error::FullBuffer), buffer.write(2));
+}
+
+#[test]
+fn blocking_buffer_waits_with_block() {
+    let buffer = Arc::new(BlockingCircularBuffer::new(1));
+    buffer.write(1).unwrap();
+    let reader = {
+        let buffer = Arc::clone(&buffer);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            buffer.read()
+        })
+    };
+    buffer.write(2).unwrap();
+    assert_eq!(reader.join().unwrap(), Ok(1));
+    assert_eq!(buffer.stats(), Stats { writes: 2, drops: 0, reads: 1 });
+}
+
+fn assert_sync<T: Sync>() {}
+fn assert_ref_unwind_safe<T: std::panic::RefUnwindSafe>() {}
+
+#[test]
+fn buffer_with_a_callback_is_still_sync() {
+    assert_sync::<CircularBuffer<i32>>();
+    assert_ref_unwind_safe::<CircularBuffer<i32>>();
+
+    let (mut buffer, evicted) = buffer(OverflowPolicy::DropOldest);
+    buffer.write(1).unwrap();
+    buffer.write(2).unwrap();
+    buffer.write(3).unwrap();
+    let buffer = &buffer;
+    thread::scope(|s| {
+        s.spawn(move || assert_eq!(contents(buffer), vec![2, 3]));
+    });
+    assert_eq!(std::panic::catch_unwind(|| buffer.len()).unwrap(), 2);
+    assert_eq!(*evicted.lock().unwrap(), vec![1]);
+}
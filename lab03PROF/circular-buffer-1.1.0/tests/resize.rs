@@ -0,0 +1,127 @@
+use circular_buffer::{CircularBuffer, Error, Keep};
+use std::rc::Rc;
+
+// capacity 4 holding 1, 2, 3, 4 wrapped around the end of the slots
+fn wrapped() -> CircularBuffer<i32> {
+    let mut buffer = CircularBuffer::new(4);
+    for i in [-1, 0, 1, 2] {
+        buffer.write(i).unwrap();
+    }
+    buffer.read().unwrap();
+    buffer.read().unwrap();
+    buffer.write(3).unwrap();
+    buffer.write(4).unwrap();
+    buffer
+}
+
+fn contents(buffer: &CircularBuffer<i32>) -> Vec<i32> {
+    buffer.iter().copied().collect()
+}
+
+#[test]
+fn zero_capacity() {
+    let mut buffer = CircularBuffer::new(0);
+    assert_eq!(Err(Error::FullBuffer), buffer.write(1));
+    assert_eq!(Err(Error::EmptyBuffer), buffer.read());
+    buffer.overwrite(2);
+    buffer.clear();
+    buffer.make_contiguous();
+    assert!(buffer.is_empty());
+    assert_eq!(&*buffer, &[] as &[i32]);
+}
+
+#[test]
+fn reserve_keeps_the_order() {
+    let mut buffer = wrapped();
+    buffer.reserve(2);
+    assert_eq!(buffer.capacity(), 6);
+    assert!(buffer.write(5).is_ok());
+    assert!(buffer.write(6).is_ok());
+    assert_eq!(Err(Error::FullBuffer), buffer.write(7));
+    assert_eq!(contents(&buffer), vec![1, 2, 3, 4, 5, 6]);
+
+    // already enough room
+    buffer.read().unwrap();
+    buffer.reserve(1);
+    assert_eq!(buffer.capacity(), 6);
+}
+
+#[test]
+fn resize_keeping_the_newest() {
+    let mut buffer = wrapped();
+    buffer.resize(2, Keep::Newest);
+    assert_eq!(buffer.capacity(), 2);
+    assert_eq!(contents(&buffer), vec![3, 4]);
+    buffer.overwrite(5);
+    assert_eq!(contents(&buffer), vec![4, 5]);
+}
+
+#[test]
+fn resize_keeping_the_oldest() {
+    let mut buffer = wrapped();
+    buffer.resize(3, Keep::Oldest);
+    assert_eq!(contents(&buffer), vec![1, 2, 3]);
+    assert_eq!(Err(Error::FullBuffer), buffer.write(5));
+    assert_eq!(Ok(1), buffer.read());
+}
+
+#[test]
+fn resize_drops_the_elements_removed() {
+    let element = Rc::new(());
+    let mut buffer = CircularBuffer::new(4);
+    for _ in 0..4 {
+        buffer.write(Rc::clone(&element)).unwrap();
+    }
+    buffer.resize(1, Keep::Newest);
+    assert_eq!(Rc::strong_count(&element), 2);
+    buffer.resize(0, Keep::Oldest);
+    assert_eq!(Rc::strong_count(&element), 1);
+    assert_eq!(buffer.capacity(), 0);
+}
+
+#[test]
+fn grow_and_shrink_to_fit() {
+    let mut buffer = wrapped();
+    buffer.resize(8, Keep::Oldest);
+    assert_eq!(contents(&buffer), vec![1, 2, 3, 4]);
+    buffer.write(5).unwrap();
+    buffer.read().unwrap();
+    buffer.shrink_to_fit();
+    assert_eq!(buffer.capacity(), 4);
+    assert_eq!(contents(&buffer), vec![2, 3, 4, 5]);
+    assert_eq!(Err(Error::FullBuffer), buffer.write(6));
+}
+
+#[test]
+fn auto_grow() {
+    let mut buffer = CircularBuffer::growable(0);
+    for i in 0..10 {
+        assert!(buffer.write(i).is_ok());
+        if i % 3 == 0 {
+            buffer.overwrite(-i);
+        }
+    }
+    assert_eq!(buffer.capacity(), 16);
+    assert_eq!(contents(&buffer), vec![0, 0, 1, 2, 3, -3, 4, 5, 6, -6, 7, 8, 9, -9]);
+
+    // growing from a wrapped buffer
+    let mut buffer = wrapped();
+    buffer.set_auto_grow(true);
+    buffer.write(5).unwrap();
+    assert_eq!(buffer.capacity(), 8);
+    assert_eq!(contents(&buffer), vec![1, 2, 3, 4, 5]);
+
+    buffer.set_auto_grow(false);
+    for i in 6..9 {
+        buffer.write(i).unwrap();
+    }
+    assert_eq!(Err(Error::FullBuffer), buffer.write(9));
+}
+
+#[test]
+fn try_new_fails_instead_of_panicking() {
+    assert!(CircularBuffer::<u64>::try_new(usize::MAX).is_err());
+    let buffer = CircularBuffer::<u64>::try_new(3).unwrap();
+    assert_eq!(buffer.capacity(), 3);
+    assert!(buffer.is_empty());
+}
@@ -0,0 +1,42 @@
+use circular_buffer::CircularBuffer;
+
+#[test]
+fn round_trip() {
+    let mut buffer = CircularBuffer::new(4);
+    buffer.extend([0, 1, 2, 3]);
+    buffer.read().unwrap();
+    buffer.overwrite(4);
+    buffer.overwrite(5);
+
+    let json = serde_json::to_string(&buffer).unwrap();
+    assert_eq!(json, r#"{"capacity":4,"elements":[2,3,4,5]}"#);
+    let back: CircularBuffer<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, buffer);
+    assert_eq!(back.capacity(), 4);
+}
+
+#[test]
+fn free_room_is_kept() {
+    let back: CircularBuffer<String> =
+        serde_json::from_str(r#"{"capacity":3,"elements":["a"]}"#).unwrap();
+    assert_eq!(back.capacity(), 3);
+    assert_eq!(back.len(), 1);
+    assert_eq!(back[0], "a");
+}
+
+#[test]
+fn too_many_elements() {
+    let err = serde_json::from_str::<CircularBuffer<i32>>(r#"{"capacity":1,"elements":[1,2]}"#)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("2 elements in a buffer of capacity 1"));
+}
+
+#[test]
+fn capacity_too_big() {
+    let err = serde_json::from_str::<CircularBuffer<i32>>(r#"{"capacity":18446744073709551615,"elements":[]}"#)
+        .unwrap_err();
+    assert!(err.to_string().contains("capacity 18446744073709551615"));
+    assert!(serde_json::from_str::<CircularBuffer<u64>>(r#"{"capacity":1152921504606846976,"elements":[1]}"#).is_err());
+}
@@ -0,0 +1,166 @@
+use circular_buffer::{spsc, CircularBuffer, Error};
+use std::rc::Rc;
+use std::thread;
+
+// a small random generator, enough to mix the operations
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+}
+
+#[test]
+fn same_semantics_as_circular_buffer() {
+    let (mut tx, mut rx) = spsc::channel(2);
+    assert_eq!(Err(Error::EmptyBuffer), rx.read());
+    assert!(tx.write('1').is_ok());
+    assert!(tx.write('2').is_ok());
+    assert_eq!(Err(Error::FullBuffer), tx.write('3'));
+    assert!(tx.is_full());
+    tx.overwrite('A');
+    assert_eq!(Ok('2'), rx.read());
+    assert_eq!(Ok('A'), rx.read());
+    assert_eq!(Err(Error::EmptyBuffer), rx.read());
+}
+
+#[test]
+fn zero_capacity() {
+    let (mut tx, mut rx) = spsc::channel(0);
+    assert_eq!(Err(Error::FullBuffer), tx.write(1));
+    tx.overwrite(2);
+    assert_eq!(tx.write_slice(&[1, 2]), 0);
+    assert_eq!(Err(Error::EmptyBuffer), rx.read());
+    assert_eq!(rx.read_slice(&mut [0; 2]), 0);
+}
+
+// random operations on one thread give the same results as CircularBuffer
+#[test]
+fn single_threaded_model() {
+    let mut rng = Lcg(42);
+    let mut model = CircularBuffer::new(5);
+    let (mut tx, mut rx) = spsc::channel(5);
+    for i in 0..10_000 {
+        match rng.next() % 5 {
+            0 => assert_eq!(tx.write(i), model.write(i)),
+            1 => {
+                tx.overwrite(i);
+                model.overwrite(i);
+            }
+            2 => {
+                let n = (rng.next() % 7) as usize;
+                let src: Vec<_> = (i..i + n as u64).collect();
+                let written = tx.write_slice(&src);
+                assert_eq!(written, n.min(model.capacity() - model.len()));
+                for &x in &src[..written] {
+                    model.write(x).unwrap();
+                }
+            }
+            3 => assert_eq!(rx.read(), model.read()),
+            _ => {
+                let mut buf = vec![0; (rng.next() % 7) as usize];
+                let n = rx.read_slice(&mut buf);
+                let expected: Vec<_> = model.drain(..n).collect();
+                assert_eq!(&buf[..n], &expected[..]);
+                assert!(n == buf.len() || model.is_empty());
+            }
+        }
+        assert_eq!(tx.len(), model.len());
+    }
+}
+
+#[test]
+fn drop_frees_the_elements_left() {
+    let element = Rc::new(());
+    let (mut tx, mut rx) = spsc::channel(3);
+    for _ in 0..3 {
+        tx.write(Rc::clone(&element)).unwrap();
+    }
+    tx.overwrite(Rc::clone(&element));
+    assert_eq!(Rc::strong_count(&element), 4);
+    drop(rx.read());
+    drop(tx);
+    assert_eq!(Rc::strong_count(&element), 3);
+    drop(rx);
+    assert_eq!(Rc::strong_count(&element), 1);
+}
+
+const N: u64 = 200_000;
+
+// everything written arrives once and in order, as with CircularBuffer
+#[test]
+fn stress_write_read() {
+    let (mut tx, mut rx) = spsc::channel(64);
+    let producer = thread::spawn(move || {
+        for i in 0..N {
+            while tx.write(Box::new(i)).is_err() {
+                thread::yield_now();
+            }
+        }
+    });
+    let mut expected = 0;
+    while expected < N {
+        match rx.read() {
+            Ok(x) => {
+                assert_eq!(*x, expected);
+                expected += 1;
+            }
+            Err(_) => thread::yield_now(),
+        }
+    }
+    producer.join().unwrap();
+    assert_eq!(rx.read(), Err(Error::EmptyBuffer));
+}
+
+#[test]
+fn stress_slices() {
+    let (mut tx, mut rx) = spsc::channel(100);
+    let producer = thread::spawn(move || {
+        let data: Vec<u64> = (0..N).collect();
+        let mut sent = 0;
+        while sent < data.len() {
+            let end = (sent + 37).min(data.len());
+            match tx.write_slice(&data[sent..end]) {
+                0 => thread::yield_now(),
+                n => sent += n,
+            }
+        }
+    });
+    let mut received = Vec::new();
+    let mut buf = [0; 41];
+    while received.len() < N as usize {
+        match rx.read_slice(&mut buf) {
+            0 => thread::yield_now(),
+            n => received.extend_from_slice(&buf[..n]),
+        }
+    }
+    producer.join().unwrap();
+    assert!(received.iter().copied().eq(0..N));
+}
+
+// with overwrite some elements are lost, but what arrives is still in order and
+// the last one is never lost
+#[test]
+fn stress_overwrite() {
+    let (mut tx, mut rx) = spsc::channel(8);
+    let producer = thread::spawn(move || {
+        for i in 0..N {
+            tx.overwrite(Box::new(i));
+        }
+    });
+    let mut last = None;
+    loop {
+        match rx.read() {
+            Ok(x) => {
+                assert!(last < Some(*x));
+                last = Some(*x);
+            }
+            Err(_) if producer.is_finished() && rx.is_empty() => break,
+            Err(_) => thread::yield_now(),
+        }
+    }
+    producer.join().unwrap();
+    assert_eq!(last, Some(N - 1));
+}
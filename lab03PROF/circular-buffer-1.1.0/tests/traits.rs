@@ -0,0 +1,85 @@
+use circular_buffer::{CircularBuffer, OverflowPolicy};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+fn hash<T: Hash>(x: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    x.hash(&mut hasher);
+    hasher.finish()
+}
+
+// 1, 2, 3 with the head in the middle of the slots
+fn wrapped() -> CircularBuffer<i32> {
+    let mut buffer = CircularBuffer::new(4);
+    buffer.extend([0, 0, 1, 2]);
+    buffer.read().unwrap();
+    buffer.read().unwrap();
+    buffer.write(3).unwrap();
+    buffer
+}
+
+#[test]
+fn equality_ignores_the_head_offset() {
+    let straight: CircularBuffer<i32> = [1, 2, 3].into_iter().collect();
+    assert_eq!(straight, wrapped());
+    assert_eq!(hash(&straight), hash(&wrapped()));
+
+    let mut different = wrapped();
+    different[2] = 4;
+    assert_ne!(straight, different);
+    let shorter: CircularBuffer<i32> = [1, 2].into_iter().collect();
+    assert_ne!(straight, shorter);
+
+    let set: HashSet<_> = [straight, wrapped()].into_iter().collect();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn debug_prints_the_elements_in_order() {
+    assert_eq!(format!("{:?}", wrapped()), "[1, 2, 3]");
+    assert_eq!(format!("{:?}", CircularBuffer::<char>::new(2)), "[]");
+}
+
+#[test]
+fn clone_keeps_contents_and_configuration() {
+    let mut buffer = CircularBuffer::with_policy(3, OverflowPolicy::DropOldest);
+    buffer.extend(["a", "b", "c", "d"].map(String::from));
+    let mut clone = buffer.clone();
+    assert_eq!(clone, buffer);
+    assert_eq!(clone.capacity(), 3);
+    assert_eq!(clone.policy(), OverflowPolicy::DropOldest);
+    assert_eq!(clone.stats(), buffer.stats());
+
+    // independent of the original
+    clone.write("e".to_string()).unwrap();
+    assert_eq!(format!("{:?}", clone), r#"["c", "d", "e"]"#);
+    assert_eq!(format!("{:?}", buffer), r#"["b", "c", "d"]"#);
+}
+
+#[test]
+fn extend_follows_the_policy() {
+    // with Reject nothing is lost silently: the oldest goes, as by overwrite()
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&evicted);
+    let mut buffer = CircularBuffer::<i32>::new(2).on_evict(move |x| log.lock().unwrap().push(x));
+    buffer.extend(&[1, 2, 3]);
+    assert_eq!(buffer.iter().collect::<Vec<_>>(), [&2, &3]);
+    assert_eq!(*evicted.lock().unwrap(), [1]);
+
+    let mut buffer = CircularBuffer::with_policy(2, OverflowPolicy::DropOldest);
+    buffer.extend(1..=5);
+    assert_eq!(buffer.iter().collect::<Vec<_>>(), [&4, &5]);
+
+    let mut buffer = CircularBuffer::growable(1);
+    buffer.extend(1..=5);
+    assert_eq!(buffer.len(), 5);
+}
+
+#[test]
+fn collect_fits_the_elements() {
+    let buffer: CircularBuffer<_> = "abc".chars().collect();
+    assert_eq!(buffer.capacity(), 3);
+    assert_eq!(buffer.into_iter().collect::<String>(), "abc");
+}
@@ -0,0 +1,95 @@
+use circular_buffer::RollingWindow;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9 * (1.0 + a.abs().max(b.abs()))
+}
+
+#[test]
+fn empty_window() {
+    let w = RollingWindow::new(3);
+    assert!(w.is_empty());
+    assert_eq!(w.sum(), 0.0);
+    assert_eq!(w.mean(), None);
+    assert_eq!(w.variance(), None);
+    assert_eq!(w.min(), None);
+    assert_eq!(w.max(), None);
+    assert_eq!(w.median(), None);
+}
+
+#[test]
+fn keeps_the_last_samples() {
+    let mut w = RollingWindow::new(3);
+    assert_eq!(w.push(1.0), None);
+    assert_eq!(w.push(5.0), None);
+    assert_eq!(w.push(3.0), None);
+    assert_eq!(w.push(2.0), Some(1.0));
+    assert_eq!(w.samples().copied().collect::<Vec<_>>(), vec![5.0, 3.0, 2.0]);
+    assert_eq!(w.sum(), 10.0);
+    assert_eq!(w.min(), Some(2.0));
+    assert_eq!(w.max(), Some(5.0));
+    assert_eq!(w.push(1.0), Some(5.0));
+    assert_eq!(w.max(), Some(3.0));
+    assert_eq!(w.min(), Some(1.0));
+}
+
+#[test]
+fn mean_and_variance() {
+    let mut w = RollingWindow::new(4);
+    for x in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+        w.push(x);
+    }
+    // 5, 5, 7, 9
+    assert_eq!(w.mean(), Some(6.5));
+    assert!(close(w.variance().unwrap(), 2.75));
+    assert!(close(w.std_dev().unwrap(), 2.75f64.sqrt()));
+}
+
+#[test]
+fn percentiles() {
+    let mut w = RollingWindow::new(5);
+    for x in [10.0, 50.0, 20.0, 40.0, 30.0] {
+        w.push(x);
+    }
+    assert_eq!(w.percentile(0.0), Some(10.0));
+    assert_eq!(w.percentile(100.0), Some(50.0));
+    assert_eq!(w.median(), Some(30.0));
+    assert_eq!(w.percentile(90.0), Some(46.0));
+}
+
+#[test]
+fn zero_size_and_clear() {
+    let mut w = RollingWindow::new(0);
+    assert_eq!(w.push(1.0), Some(1.0));
+    assert!(w.is_empty());
+
+    let mut w = RollingWindow::new(2);
+    w.push(1.0);
+    w.clear();
+    assert_eq!(w.max(), None);
+    w.push(-1.0);
+    assert_eq!(w.max(), Some(-1.0));
+}
+
+// every statistic matches a loop over the window, as we used to do
+#[test]
+fn matches_recomputing_from_scratch() {
+    let mut seed = 7u64;
+    let mut w = RollingWindow::new(17);
+    let mut all = Vec::new();
+    for _ in 0..2000 {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let x = (seed >> 40) as f64 / 1000.0 - 8000.0;
+        w.push(x);
+        all.push(x);
+
+        let window = &all[all.len().saturating_sub(17)..];
+        let n = window.len() as f64;
+        let mean = window.iter().sum::<f64>() / n;
+        let variance = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        assert!(close(w.sum(), window.iter().sum()));
+        assert!(close(w.mean().unwrap(), mean));
+        assert!((w.variance().unwrap() - variance).abs() < 1e-6 * (1.0 + variance));
+        assert_eq!(w.min(), window.iter().copied().reduce(f64::min));
+        assert_eq!(w.max(), window.iter().copied().reduce(f64::max));
+    }
+}
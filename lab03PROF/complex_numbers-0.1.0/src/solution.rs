@@ -1,44 +1,434 @@
-use std::{fmt::Display, hash::{Hash, Hasher}, ops::{Add, AddAssign}};
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+// the components can be any number: f64 (the default, as before), f32 for
+// compact data, integers for Gaussian integers (a + bi with a and b integers)
+// each impl asks only what it needs of T, e.g. Add needs T: Add
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ComplexNumber<T = f64> {
+    real: T,
+    imag: T,
+}
+
+pub type Complex32 = ComplexNumber<f32>;
+pub type Complex64 = ComplexNumber<f64>;
+
+// what every primitive number can do, but not with the same std trait:
+// floats have neither Ord nor Hash, so here they use total_cmp and to_bits
+// (the zero is Default)
+pub trait Scalar: Copy + PartialEq + Default {
+    fn one() -> Self;
+    fn scalar_cmp(&self, other: &Self) -> Ordering;
+    fn scalar_hash<H: Hasher>(&self, state: &mut H);
+}
+
+// the scalars with real division and the real functions: f32 and f64
+// (the methods are the ones of f64, see there)
+pub trait Float:
+    Scalar
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const MAX: Self;
+
+    fn from_f64(x: f64) -> Self;
+    fn abs(self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn asinh(self) -> Self;
+}
+
+macro_rules! integer_scalar {
+    ($($t:ty)*) => {$(
+        impl Scalar for $t {
+            fn one() -> Self {
+                1
+            }
+
+            fn scalar_cmp(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+
+            fn scalar_hash<H: Hasher>(&self, state: &mut H) {
+                self.hash(state);
+            }
+        }
+    )*};
+}
+
+integer_scalar!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+macro_rules! float_scalar {
+    ($($t:ident)*) => {$(
+        impl Scalar for $t {
+            fn one() -> Self {
+                1.0
+            }
+
+            fn scalar_cmp(&self, other: &Self) -> Ordering {
+                self.total_cmp(other)
+            }
+
+            fn scalar_hash<H: Hasher>(&self, state: &mut H) {
+                self.to_bits().hash(state);
+            }
+        }
+
+        impl Float for $t {
+            const MAX: Self = $t::MAX;
+
+            fn from_f64(x: f64) -> Self {
+                x as $t
+            }
+
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+
+            fn copysign(self, sign: Self) -> Self {
+                $t::copysign(self, sign)
+            }
+
+            fn sqrt(self) -> Self {
+                $t::sqrt(self)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                $t::hypot(self, other)
+            }
+
+            fn powf(self, n: Self) -> Self {
+                $t::powf(self, n)
+            }
+
+            fn exp(self) -> Self {
+                $t::exp(self)
+            }
 
-#[derive(Debug)]
-pub struct ComplexNumber {
-    real: f64,
-    imag: f64,
+            fn ln(self) -> Self {
+                $t::ln(self)
+            }
+
+            fn ln_1p(self) -> Self {
+                $t::ln_1p(self)
+            }
+
+            fn sin(self) -> Self {
+                $t::sin(self)
+            }
+
+            fn cos(self) -> Self {
+                $t::cos(self)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                $t::atan2(self, other)
+            }
+
+            fn sinh(self) -> Self {
+                $t::sinh(self)
+            }
+
+            fn cosh(self) -> Self {
+                $t::cosh(self)
+            }
+
+            fn asinh(self) -> Self {
+                $t::asinh(self)
+            }
+        }
+    )*};
 }
 
-impl ComplexNumber {
-    pub fn new(real: f64, imag: f64) -> ComplexNumber {
+float_scalar!(f32 f64);
+
+impl<T> ComplexNumber<T> {
+    pub fn new(real: T, imag: T) -> Self {
         ComplexNumber { real, imag }
     }
+}
 
-    pub fn from_real(real: f64) -> ComplexNumber {
-        ComplexNumber { real, imag: 0.0 }
+impl<T: Default> ComplexNumber<T> {
+    pub fn from_real(real: T) -> Self {
+        ComplexNumber {
+            real,
+            imag: T::default(),
+        }
     }
+}
 
-    pub fn real(&self) -> f64 {
+impl<T: Copy> ComplexNumber<T> {
+    pub fn real(&self) -> T {
         self.real
     }
 
-    pub fn imag(&self) -> f64 {
+    pub fn imag(&self) -> T {
         self.imag
     }
 
-    pub fn to_tuple(&self) -> (f64, f64) {
+    pub fn to_tuple(&self) -> (T, T) {
         (self.real, self.imag)
     }
+}
+
+impl<T: Copy + Neg<Output = T>> ComplexNumber<T> {
+    // the conjugate, a - bi
+    pub fn conj(&self) -> Self {
+        ComplexNumber::new(self.real, -self.imag)
+    }
+}
 
-    pub fn modulus(&self) -> f64 {
-        (self.real * self.real + self.imag * self.imag).sqrt()
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> ComplexNumber<T> {
+    // the squared modulus, a^2 + b^2: cheaper than modulus() and exact for integers
+    pub fn norm_sqr(&self) -> T {
+        self.real * self.real + self.imag * self.imag
     }
 }
 
-impl Display for ComplexNumber {
+// the functions of a complex variable
+//
+// the multivalued ones (ln, sqrt, powers, the inverse functions) return the
+// principal value and have their branch cuts where C99 and most libraries put them:
+// ln, sqrt, powf, powc, acosh on the negative real axis (acosh also on (-1, 1)),
+// asin, acos, atanh on the real axis beyond -1 and 1, asinh and atan on the
+// imaginary axis beyond -i and i. on a cut the sign of the zero tells the side:
+// sqrt(-4 + 0i) = 2i but sqrt(-4 - 0i) = -2i, as if coming from above or below
+//
+// the inverse functions use Kahan's formulas (Branch Cuts for Complex Elementary
+// Functions, 1987) or rotations by i (which only swap components and signs, so
+// the zeros keep their sign)
+impl<T: Float> ComplexNumber<T> {
+    // |z|; hypot doesn't overflow when a^2 + b^2 would
+    pub fn modulus(&self) -> T {
+        self.real.hypot(self.imag)
+    }
+
+    // the angle from the positive real axis, in (-pi, pi]
+    pub fn arg(&self) -> T {
+        self.imag.atan2(self.real)
+    }
+
+    pub fn from_polar(r: T, theta: T) -> Self {
+        ComplexNumber::new(r * theta.cos(), r * theta.sin())
+    }
+
+    // (modulus, arg)
+    pub fn to_polar(&self) -> (T, T) {
+        (self.modulus(), self.arg())
+    }
+
+    // i * z and -i * z
+    fn mul_i(&self) -> Self {
+        ComplexNumber::new(-self.imag, self.real)
+    }
+
+    fn mul_neg_i(&self) -> Self {
+        ComplexNumber::new(self.imag, -self.real)
+    }
+
+    fn one() -> Self {
+        ComplexNumber::from_real(T::one())
+    }
+
+    pub fn exp(&self) -> Self {
+        // a real exponent stays real (exp(inf) * sin(0) would be NaN)
+        if self.imag == T::default() {
+            return ComplexNumber::new(self.real.exp(), self.imag);
+        }
+        ComplexNumber::from_polar(self.real.exp(), self.imag)
+    }
+
+    // the natural logarithm: ln|z| + i arg(z)
+    pub fn ln(&self) -> Self {
+        ComplexNumber::new(self.modulus().ln(), self.arg())
+    }
+
+    pub fn log(&self, base: T) -> Self {
+        self.ln() / base.ln()
+    }
+
+    // by repeated squaring, exact for small integers; with n < 0 the base is
+    // inverted first, a result too big to invert would give NaN
+    pub fn powi(&self, n: i32) -> Self {
+        let mut base = if n < 0 { Self::one() / *self } else { *self };
+        let mut result = Self::one();
+        let mut e = n.unsigned_abs();
+        while e > 0 {
+            if e & 1 == 1 {
+                result *= base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        result
+    }
+
+    pub fn powf(&self, x: T) -> Self {
+        let (r, theta) = self.to_polar();
+        ComplexNumber::from_polar(r.powf(x), theta * x)
+    }
+
+    // z^w = exp(w ln z), with 0^w = 0 (ln 0 is -inf) and z^0 = 1
+    pub fn powc(&self, w: Self) -> Self {
+        let zero = T::default();
+        if w.real == zero && w.imag == zero {
+            return Self::one();
+        }
+        if self.real == zero && self.imag == zero {
+            return ComplexNumber::default();
+        }
+        (w * self.ln()).exp()
+    }
+
+    // the principal square root, with a real part >= 0; computed from |z| + |a|,
+    // never |z| - |a| which would cancel
+    pub fn sqrt(&self) -> Self {
+        let zero = T::default();
+        let two = T::one() + T::one();
+        let (a, b) = (self.real, self.imag);
+        if a == zero && b == zero {
+            return ComplexNumber::new(zero, b);
+        }
+        let m = self.modulus();
+        if m > T::MAX / two && m <= T::MAX {
+            // |z| + |a| would overflow: sqrt(z) = 2 sqrt(z / 4), exact in binary
+            let four = two * two;
+            let root = ComplexNumber::new(a / four, b / four).sqrt();
+            return ComplexNumber::new(root.real * two, root.imag * two);
+        }
+        let t = ((m + a.abs()) / two).sqrt();
+        if a >= zero {
+            ComplexNumber::new(t, b / (two * t))
+        } else {
+            ComplexNumber::new(b.abs() / (two * t), t.copysign(b))
+        }
+    }
+
+    pub fn sin(&self) -> Self {
+        let (a, b) = (self.real, self.imag);
+        ComplexNumber::new(a.sin() * b.cosh(), a.cos() * b.sinh())
+    }
+
+    pub fn cos(&self) -> Self {
+        let (a, b) = (self.real, self.imag);
+        ComplexNumber::new(a.cos() * b.cosh(), -(a.sin() * b.sinh()))
+    }
+
+    // tan(z) = -i tanh(iz)
+    pub fn tan(&self) -> Self {
+        self.mul_i().tanh().mul_neg_i()
+    }
+
+    pub fn sinh(&self) -> Self {
+        let (a, b) = (self.real, self.imag);
+        ComplexNumber::new(a.sinh() * b.cos(), a.cosh() * b.sin())
+    }
+
+    pub fn cosh(&self) -> Self {
+        let (a, b) = (self.real, self.imag);
+        ComplexNumber::new(a.cosh() * b.cos(), a.sinh() * b.sin())
+    }
+
+    pub fn tanh(&self) -> Self {
+        let two = T::one() + T::one();
+        let (a, b) = (self.real, self.imag);
+        // beyond that cosh(2a) overflows, but tanh(z) is already 1 (or -1)
+        if a.abs() > T::from_f64(20.0) {
+            let imag = two * (two * b).sin() * (-two * a.abs()).exp();
+            return ComplexNumber::new(T::one().copysign(a), imag);
+        }
+        let d = (two * a).cosh() + (two * b).cos();
+        ComplexNumber::new((two * a).sinh() / d, (two * b).sin() / d)
+    }
+
+    pub fn asin(&self) -> Self {
+        let one = T::one();
+        // sqrt(1 - z) and sqrt(1 + z), written so that the sign of b goes through
+        let s1 = ComplexNumber::new(one - self.real, -self.imag).sqrt();
+        let s2 = ComplexNumber::new(one + self.real, self.imag).sqrt();
+        ComplexNumber::new(
+            self.real.atan2(s1.real * s2.real - s1.imag * s2.imag),
+            (s1.real * s2.imag - s1.imag * s2.real).asinh(),
+        )
+    }
+
+    pub fn acos(&self) -> Self {
+        let one = T::one();
+        let two = one + one;
+        let s1 = ComplexNumber::new(one - self.real, -self.imag).sqrt();
+        let s2 = ComplexNumber::new(one + self.real, self.imag).sqrt();
+        ComplexNumber::new(
+            two * s1.real.atan2(s2.real),
+            (s2.real * s1.imag - s2.imag * s1.real).asinh(),
+        )
+    }
+
+    // atan(z) = -i atanh(iz)
+    pub fn atan(&self) -> Self {
+        self.mul_i().atanh().mul_neg_i()
+    }
+
+    // asinh(z) = -i asin(iz)
+    pub fn asinh(&self) -> Self {
+        self.mul_i().asin().mul_neg_i()
+    }
+
+    pub fn acosh(&self) -> Self {
+        let one = T::one();
+        let two = one + one;
+        // sqrt(z - 1) and sqrt(z + 1)
+        let s1 = ComplexNumber::new(self.real - one, self.imag).sqrt();
+        let s2 = ComplexNumber::new(self.real + one, self.imag).sqrt();
+        ComplexNumber::new(
+            (s1.real * s2.real + s1.imag * s2.imag).asinh(),
+            two * s1.imag.atan2(s2.real),
+        )
+    }
+
+    // (ln(1 + z) - ln(1 - z)) / 2, rearranged to avoid the cancellations near 0
+    pub fn atanh(&self) -> Self {
+        let one = T::one();
+        let two = one + one;
+        let four = two + two;
+        let (a, b) = (self.real, self.imag);
+        let den = (one - a) * (one - a) + b * b;
+        ComplexNumber::new(
+            (four * a / den).ln_1p() / four,
+            (two * b).atan2((one - a) * (one + a) - b * b) / two,
+        )
+    }
+}
+
+impl<T: Display> Display for ComplexNumber<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} + {}i", self.real, self.imag)
     }
 }
 
-impl Add for ComplexNumber {
+// the arithmetic: each operator is written once for values, the macros below
+// add the variants with references, with a scalar on the right (z * 2.0 scales
+// both components) and the *Assign versions
+
+impl<T: Add<Output = T>> Add for ComplexNumber<T> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -49,22 +439,53 @@ impl Add for ComplexNumber {
     }
 }
 
-impl Add<&ComplexNumber> for ComplexNumber {
+impl<T: Sub<Output = T>> Sub for ComplexNumber<T> {
     type Output = Self;
 
-    fn add(self, rhs: &Self) -> Self::Output {
+    fn sub(self, rhs: Self) -> Self::Output {
         ComplexNumber {
-            real: self.real + rhs.real,
-            imag: self.imag + rhs.imag,
+            real: self.real - rhs.real,
+            imag: self.imag - rhs.imag,
         }
     }
 }
 
+// (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Mul for ComplexNumber<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        ComplexNumber {
+            real: self.real * rhs.real - self.imag * rhs.imag,
+            imag: self.real * rhs.imag + self.imag * rhs.real,
+        }
+    }
+}
+
+// Smith's algorithm: the textbook formula divides by c^2 + d^2, which overflows
+// (or underflows to 0) long before the result does; dividing first by the larger
+// of c and d keeps the numbers in range
+impl<T: Float> Div for ComplexNumber<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let (a, b, c, d) = (self.real, self.imag, rhs.real, rhs.imag);
+        if c.abs() >= d.abs() {
+            let r = d / c;
+            let den = c + d * r;
+            ComplexNumber::new((a + b * r) / den, (b - a * r) / den)
+        } else {
+            let r = c / d;
+            let den = c * r + d;
+            ComplexNumber::new((a * r + b) / den, (b * r - a) / den)
+        }
+    }
+}
 
-impl Add<f64> for ComplexNumber {
+impl<T: Add<Output = T>> Add<T> for ComplexNumber<T> {
     type Output = Self;
 
-    fn add(self, rhs: f64) -> Self::Output {
+    fn add(self, rhs: T) -> Self::Output {
         ComplexNumber {
             real: self.real + rhs,
             ..self
@@ -72,36 +493,204 @@ impl Add<f64> for ComplexNumber {
     }
 }
 
-impl Add<&ComplexNumber> for &ComplexNumber {
-    type Output = ComplexNumber;
+impl<T: Sub<Output = T>> Sub<T> for ComplexNumber<T> {
+    type Output = Self;
 
-    fn add(self, rhs: &ComplexNumber) -> Self::Output {
+    fn sub(self, rhs: T) -> Self::Output {
         ComplexNumber {
-            real: self.real + rhs.real,
-            imag: self.imag + rhs.imag,
+            real: self.real - rhs,
+            ..self
         }
     }
 }
 
-impl AddAssign for ComplexNumber {
-    fn add_assign(&mut self, rhs: Self) {
-        self.real += rhs.real;
-        self.imag += rhs.imag;
+impl<T: Copy + Mul<Output = T>> Mul<T> for ComplexNumber<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        ComplexNumber::new(self.real * rhs, self.imag * rhs)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for ComplexNumber<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        ComplexNumber::new(self.real / rhs, self.imag / rhs)
     }
 }
 
+impl<T: Neg<Output = T>> Neg for ComplexNumber<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        ComplexNumber::new(-self.real, -self.imag)
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Neg for &ComplexNumber<T> {
+    type Output = ComplexNumber<T>;
+
+    fn neg(self) -> Self::Output {
+        -*self
+    }
+}
+
+// the operator with references on either side, and the *Assign version:
+// complex_ops!(Op, op, OpAssign, op_assign, [bounds with a complex rhs], [with a scalar rhs])
+macro_rules! complex_ops {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, [$($c:tt)+], [$($s:tt)+]) => {
+        impl<T: Copy + $($c)+> $Op<&ComplexNumber<T>> for ComplexNumber<T> {
+            type Output = ComplexNumber<T>;
+
+            fn $op(self, rhs: &ComplexNumber<T>) -> Self::Output {
+                $Op::$op(self, *rhs)
+            }
+        }
+
+        impl<T: Copy + $($c)+> $Op<ComplexNumber<T>> for &ComplexNumber<T> {
+            type Output = ComplexNumber<T>;
+
+            fn $op(self, rhs: ComplexNumber<T>) -> Self::Output {
+                $Op::$op(*self, rhs)
+            }
+        }
+
+        impl<T: Copy + $($c)+> $Op<&ComplexNumber<T>> for &ComplexNumber<T> {
+            type Output = ComplexNumber<T>;
+
+            fn $op(self, rhs: &ComplexNumber<T>) -> Self::Output {
+                $Op::$op(*self, *rhs)
+            }
+        }
+
+        impl<T: Copy + $($s)+> $Op<&T> for ComplexNumber<T> {
+            type Output = ComplexNumber<T>;
+
+            fn $op(self, rhs: &T) -> Self::Output {
+                $Op::$op(self, *rhs)
+            }
+        }
+
+        impl<T: Copy + $($s)+> $Op<T> for &ComplexNumber<T> {
+            type Output = ComplexNumber<T>;
+
+            fn $op(self, rhs: T) -> Self::Output {
+                $Op::$op(*self, rhs)
+            }
+        }
+
+        impl<T: Copy + $($s)+> $Op<&T> for &ComplexNumber<T> {
+            type Output = ComplexNumber<T>;
+
+            fn $op(self, rhs: &T) -> Self::Output {
+                $Op::$op(*self, *rhs)
+            }
+        }
+
+        impl<T: Copy + $($c)+> $OpAssign for ComplexNumber<T> {
+            fn $op_assign(&mut self, rhs: Self) {
+                *self = $Op::$op(*self, rhs);
+            }
+        }
+
+        impl<T: Copy + $($c)+> $OpAssign<&ComplexNumber<T>> for ComplexNumber<T> {
+            fn $op_assign(&mut self, rhs: &Self) {
+                *self = $Op::$op(*self, *rhs);
+            }
+        }
+
+        impl<T: Copy + $($s)+> $OpAssign<T> for ComplexNumber<T> {
+            fn $op_assign(&mut self, rhs: T) {
+                *self = $Op::$op(*self, rhs);
+            }
+        }
+
+        impl<T: Copy + $($s)+> $OpAssign<&T> for ComplexNumber<T> {
+            fn $op_assign(&mut self, rhs: &T) {
+                *self = $Op::$op(*self, *rhs);
+            }
+        }
+    };
+}
+
+complex_ops!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    [Add<Output = T>],
+    [Add<Output = T>]
+);
+complex_ops!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    [Sub<Output = T>],
+    [Sub<Output = T>]
+);
+complex_ops!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    [Add<Output = T> + Sub<Output = T> + Mul<Output = T>],
+    [Mul<Output = T>]
+);
+complex_ops!(Div, div, DivAssign, div_assign, [Float], [Div<Output = T>]);
+
+// a scalar on the left, 2.0 * z: the impl is for the scalar type, which isn't
+// ours, so it can't be generic over T, one for each type
+macro_rules! scalar_lhs_ops {
+    ($Op:ident, $op:ident, $($t:ty)*) => {$(
+        impl $Op<ComplexNumber<$t>> for $t {
+            type Output = ComplexNumber<$t>;
+
+            fn $op(self, rhs: ComplexNumber<$t>) -> Self::Output {
+                $Op::$op(ComplexNumber::from_real(self), rhs)
+            }
+        }
+
+        impl $Op<&ComplexNumber<$t>> for $t {
+            type Output = ComplexNumber<$t>;
+
+            fn $op(self, rhs: &ComplexNumber<$t>) -> Self::Output {
+                $Op::$op(ComplexNumber::from_real(self), *rhs)
+            }
+        }
+    )*};
+}
+
+scalar_lhs_ops!(Add, add, f32 f64 i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+scalar_lhs_ops!(Sub, sub, f32 f64 i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+scalar_lhs_ops!(Mul, mul, f32 f64 i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+scalar_lhs_ops!(Div, div, f32 f64);
 
-impl Clone for ComplexNumber {
-    fn clone(&self) -> Self {
-        ComplexNumber { ..*self }
+impl<T: Default + Add<Output = T>> Sum for ComplexNumber<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ComplexNumber::default(), Add::add)
     }
 }
 
-impl Copy for ComplexNumber {}
+impl<'a, T: Copy + Default + Add<Output = T> + 'a> Sum<&'a ComplexNumber<T>> for ComplexNumber<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Product for ComplexNumber<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ComplexNumber::from_real(T::one()), Mul::mul)
+    }
+}
 
-impl Default for ComplexNumber {
-    fn default() -> Self {
-        ComplexNumber::new(0.0, 0.0)
+impl<'a, T> Product<&'a ComplexNumber<T>> for ComplexNumber<T>
+where
+    T: Scalar + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + 'a,
+{
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
     }
 }
 
@@ -114,7 +703,7 @@ impl Default for ComplexNumber {
 //        }
 //        return self.real;
 //    }
-//    
+//
 //}
 
 #[derive(Debug, PartialEq)]
@@ -122,58 +711,93 @@ pub enum ComplexNumberError {
     ImaginaryNotZero,
 }
 
-impl TryInto<f64> for ComplexNumber {
-    type Error = ComplexNumberError;
-
-    fn try_into(self) -> Result<f64, Self::Error> {
-        if self.imag != 0.0 {
-            return Err(ComplexNumberError::ImaginaryNotZero);
+// TryFrom gives TryInto for free; it can't be written once for any T (T would be
+// a foreign type implementing a foreign trait), so one impl for each scalar
+macro_rules! try_from_complex {
+    ($($t:ty)*) => {$(
+        impl TryFrom<ComplexNumber<$t>> for $t {
+            type Error = ComplexNumberError;
+
+            fn try_from(c: ComplexNumber<$t>) -> Result<Self, Self::Error> {
+                if c.imag != <$t>::default() {
+                    return Err(ComplexNumberError::ImaginaryNotZero);
+                }
+                Ok(c.real)
+            }
         }
-        return Ok(self.real);
-    }
+    )*};
 }
 
-impl Into<ComplexNumber> for f64 {
-    fn into(self) -> ComplexNumber {
-        ComplexNumber::from_real(self)
+try_from_complex!(f32 f64 i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+impl<T: Default> From<T> for ComplexNumber<T> {
+    fn from(real: T) -> Self {
+        ComplexNumber::from_real(real)
     }
 }
 
-impl PartialEq for ComplexNumber {
+impl<T: PartialEq> PartialEq for ComplexNumber<T> {
     fn eq(&self, other: &Self) -> bool {
         self.real == other.real && self.imag == other.imag
     }
 }
 
-impl Eq for ComplexNumber {}
+impl<T: Scalar> Eq for ComplexNumber<T> {}
+
+// Ord (used by sort) is lexicographic for every component type; < and > are not
+// the same for all of them, so there's an impl for each:
+// - floats compare the modulus, as before: if |a| > |b| then a > b, so
+//   (3, 0) < (0, 5) but (0, 5) comes first when sorting
+// - integers follow Ord, as a derived PartialOrd would
+macro_rules! complex_order {
+    (by modulus: $($t:ty)*) => {$(
+        #[allow(clippy::non_canonical_partial_ord_impl)]
+        impl PartialOrd for ComplexNumber<$t> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.modulus().partial_cmp(&other.modulus())
+            }
+        }
 
-impl PartialOrd for ComplexNumber {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.modulus().partial_cmp(&other.modulus())
-    }
-}
+        complex_order!(@ord $t);
+    )*};
+    (as Ord: $($t:ty)*) => {$(
+        impl PartialOrd for ComplexNumber<$t> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
 
-impl Ord for ComplexNumber {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        return self.real.total_cmp(&other.real).then_with(|| self.imag.total_cmp(&other.imag));
-    }
+        complex_order!(@ord $t);
+    )*};
+    (@ord $t:ty) => {
+        impl Ord for ComplexNumber<$t> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.real
+                    .scalar_cmp(&other.real)
+                    .then_with(|| self.imag.scalar_cmp(&other.imag))
+            }
+        }
+    };
 }
 
-impl AsRef<f64> for ComplexNumber {
-    fn as_ref(&self) -> &f64 {
+complex_order!(by modulus: f32 f64);
+complex_order!(as Ord: i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+impl<T> AsRef<T> for ComplexNumber<T> {
+    fn as_ref(&self) -> &T {
         &self.real
     }
 }
 
-impl AsMut<f64> for ComplexNumber {
-    fn as_mut(&mut self) -> &mut f64 {
+impl<T> AsMut<T> for ComplexNumber<T> {
+    fn as_mut(&mut self) -> &mut T {
         &mut self.real
     }
 }
 
-impl Hash for ComplexNumber {
+impl<T: Scalar> Hash for ComplexNumber<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_u64(self.real.to_bits());
-        state.write_u64(self.imag.to_bits());
+        self.real.scalar_hash(state);
+        self.imag.scalar_hash(state);
     }
-}
\ No newline at end of file
+}
@@ -296,3 +296,384 @@ pub fn test_deque() {
         assert!(false);
     }
 }
+
+#[test]
+pub fn test_generic_f32() {
+    // the same operations with f32 components, through the Complex32 alias
+    use complex_numbers::solution::Complex32;
+
+    let a = Complex32::new(1.5, 2.0);
+    let b = a + 0.5f32;
+    assert_eq!(b.to_tuple(), (2.0f32, 2.0f32));
+    assert_eq!(Complex32::new(3.0, 4.0).modulus(), 5.0f32);
+    assert_eq!(format!("{a}"), "1.5 + 2i");
+    assert_eq!(TryInto::<f32>::try_into(Complex32::from_real(2.5)), Ok(2.5));
+}
+
+#[test]
+#[allow(clippy::op_ref)] // &a + &b on purpose, to check the impl for references
+pub fn test_gaussian_integers() {
+    // integer components: Eq, Ord and Hash come from the integers themselves
+    let mut a = ComplexNumber::new(1, -2);
+    a += ComplexNumber::new(2, 3);
+    assert_eq!(a, ComplexNumber::new(3, 1));
+    assert_eq!(&a + &ComplexNumber::from(1), ComplexNumber::new(4, 1));
+    assert_eq!(TryInto::<i32>::try_into(a), Err(ComplexNumberError::ImaginaryNotZero));
+
+    let mut v = vec![ComplexNumber::new(2, 0), ComplexNumber::new(1, 5), ComplexNumber::new(1, -5)];
+    v.sort();
+    assert_eq!(v, [ComplexNumber::new(1, -5), ComplexNumber::new(1, 5), ComplexNumber::new(2, 0)]);
+
+    let set: std::collections::HashSet<_> = v.iter().chain(v.iter()).copied().collect();
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+#[allow(clippy::op_ref)] // references on purpose, to check their impls
+pub fn test_sub() {
+    let a = ComplexNumber::new(3.0, 5.0);
+    let b = ComplexNumber::new(1.0, 2.0);
+
+    assert_eq!((a - b).to_tuple(), (2.0, 3.0));
+    assert_eq!((a - &b).to_tuple(), (2.0, 3.0));
+    assert_eq!((&a - b).to_tuple(), (2.0, 3.0));
+    assert_eq!((&a - &b).to_tuple(), (2.0, 3.0));
+}
+
+#[test]
+#[allow(clippy::op_ref)] // references on purpose, to check their impls
+pub fn test_sub_with_real() {
+    let a: ComplexNumber = ComplexNumber::new(3.0, 5.0);
+
+    assert_eq!((a - 1.0).to_tuple(), (2.0, 5.0));
+    assert_eq!((&a - &1.0).to_tuple(), (2.0, 5.0));
+    // the real on the left: 1 - (3 + 5i)
+    assert_eq!((1.0 - a).to_tuple(), (-2.0, -5.0));
+}
+
+#[test]
+#[allow(clippy::op_ref)] // references on purpose, to check their impls
+pub fn test_mul() {
+    // (1 + 2i)(3 + 4i) = 3 + 4i + 6i + 8i^2 = -5 + 10i
+    let a = ComplexNumber::new(1.0, 2.0);
+    let b = ComplexNumber::new(3.0, 4.0);
+
+    assert_eq!((a * b).to_tuple(), (-5.0, 10.0));
+    assert_eq!((a * &b).to_tuple(), (-5.0, 10.0));
+    assert_eq!((&a * b).to_tuple(), (-5.0, 10.0));
+    assert_eq!((&a * &b).to_tuple(), (-5.0, 10.0));
+
+    // i * i = -1
+    let i = ComplexNumber::new(0.0, 1.0);
+    assert_eq!(i * i, ComplexNumber::from_real(-1.0));
+}
+
+#[test]
+#[allow(clippy::op_ref)] // references on purpose, to check their impls
+pub fn test_mul_with_real_on_both_sides() {
+    let z: ComplexNumber = ComplexNumber::new(1.0, -2.0);
+
+    assert_eq!((z * 2.0).to_tuple(), (2.0, -4.0));
+    assert_eq!((2.0 * z).to_tuple(), (2.0, -4.0));
+    assert_eq!((2.0 * &z).to_tuple(), (2.0, -4.0));
+    assert_eq!((&z * 2.0).to_tuple(), (2.0, -4.0));
+}
+
+#[test]
+#[allow(clippy::op_ref)] // references on purpose, to check their impls
+pub fn test_div() {
+    // (-5 + 10i) / (3 + 4i) = 1 + 2i
+    let a = ComplexNumber::new(-5.0, 10.0);
+    let b = ComplexNumber::new(3.0, 4.0);
+
+    assert_eq!((a / b).to_tuple(), (1.0, 2.0));
+    assert_eq!((a / &b).to_tuple(), (1.0, 2.0));
+    assert_eq!((&a / b).to_tuple(), (1.0, 2.0));
+    assert_eq!((&a / &b).to_tuple(), (1.0, 2.0));
+
+    // the imaginary part larger than the real one takes the other branch
+    let c = ComplexNumber::new(4.0, 3.0);
+    let q = c / ComplexNumber::new(0.0, 1.0);
+    assert_eq!(q.to_tuple(), (3.0, -4.0));
+}
+
+#[test]
+pub fn test_div_with_real_on_both_sides() {
+    let z = ComplexNumber::new(2.0, -4.0);
+    assert_eq!((z / 2.0).to_tuple(), (1.0, -2.0));
+
+    // 1 / i = -i
+    let i: ComplexNumber = ComplexNumber::new(0.0, 1.0);
+    assert_eq!((1.0 / i).to_tuple(), (0.0, -1.0));
+}
+
+#[test]
+pub fn test_div_without_overflow() {
+    // with the textbook formula c^2 + d^2 would be infinite and the result 0,
+    // Smith's algorithm gets it right
+    let big = 1e300;
+    let a = ComplexNumber::new(big, big);
+    let b = ComplexNumber::new(big, big);
+    assert_eq!((a / b).to_tuple(), (1.0, 0.0));
+
+    let tiny = 1e-300;
+    let q = ComplexNumber::new(tiny, 0.0) / ComplexNumber::new(tiny, tiny);
+    assert_eq!(q.to_tuple(), (0.5, -0.5));
+}
+
+#[test]
+pub fn test_neg() {
+    let a = ComplexNumber::new(1.0, -2.0);
+
+    assert_eq!((-a).to_tuple(), (-1.0, 2.0));
+    assert_eq!((-&a).to_tuple(), (-1.0, 2.0));
+    assert_eq!(a + -a, ComplexNumber::default());
+}
+
+#[test]
+pub fn test_assign_operators() {
+    let mut a = ComplexNumber::new(1.0, 2.0);
+    let b = ComplexNumber::new(3.0, 4.0);
+
+    a += &b;
+    assert_eq!(a.to_tuple(), (4.0, 6.0));
+    a -= b;
+    assert_eq!(a.to_tuple(), (1.0, 2.0));
+    a *= b;
+    assert_eq!(a.to_tuple(), (-5.0, 10.0));
+    a /= &b;
+    assert_eq!(a.to_tuple(), (1.0, 2.0));
+
+    // and with a real
+    a += 1.0;
+    a -= &0.5;
+    a *= 2.0;
+    a /= 4.0;
+    assert_eq!(a.to_tuple(), (0.75, 1.0));
+}
+
+#[test]
+pub fn test_sum_and_product() {
+    let v = [
+        ComplexNumber::new(1.0, 1.0),
+        ComplexNumber::new(2.0, -1.0),
+        ComplexNumber::new(0.0, 3.0),
+    ];
+
+    let sum: ComplexNumber = v.iter().sum();
+    assert_eq!(sum.to_tuple(), (3.0, 3.0));
+    // (1 + i)(2 - i) = 3 + i, (3 + i)(3i) = -3 + 9i
+    let product: ComplexNumber = v.into_iter().product();
+    assert_eq!(product.to_tuple(), (-3.0, 9.0));
+
+    // the empty sum is 0, the empty product is 1
+    let empty: [ComplexNumber; 0] = [];
+    assert_eq!(empty.iter().sum::<ComplexNumber>().to_tuple(), (0.0, 0.0));
+    assert_eq!(empty.iter().product::<ComplexNumber>().to_tuple(), (1.0, 0.0));
+}
+
+#[test]
+pub fn test_gaussian_integer_arithmetic() {
+    // integers have no division between complex numbers, the rest works
+    let a = ComplexNumber::new(2, 3);
+    let b = ComplexNumber::new(1, -1);
+
+    assert_eq!(a * b, ComplexNumber::new(5, 1));
+    assert_eq!(3 * a - b, ComplexNumber::new(5, 10));
+    assert_eq!(ComplexNumber::new(7, 5) / 2, ComplexNumber::new(3, 2));
+    assert_eq!([a, b].iter().product::<ComplexNumber<i32>>(), a * b);
+}
+
+// floating point results are compared with a tolerance
+fn assert_close(z: ComplexNumber, expected: (f64, f64)) {
+    let close = |x: f64, y: f64| (x - y).abs() <= 1e-12 * y.abs().max(1.0) || x == y;
+    assert!(
+        close(z.real(), expected.0) && close(z.imag(), expected.1),
+        "{z} is not {} + {}i",
+        expected.0,
+        expected.1
+    );
+}
+
+#[test]
+pub fn test_conj_norm_arg() {
+    let z = ComplexNumber::new(3.0, -4.0);
+
+    assert_eq!(z.conj().to_tuple(), (3.0, 4.0));
+    assert_eq!(z.norm_sqr(), 25.0);
+    assert_eq!((z * z.conj()).to_tuple(), (25.0, 0.0));
+    assert_eq!(ComplexNumber::new(-1.0, 0.0).arg(), std::f64::consts::PI);
+    assert_eq!(ComplexNumber::new(0.0, -2.0).arg(), -std::f64::consts::FRAC_PI_2);
+
+    // also for Gaussian integers
+    assert_eq!(ComplexNumber::new(2, 3).conj(), ComplexNumber::new(2, -3));
+    assert_eq!(ComplexNumber::new(2, 3).norm_sqr(), 13);
+}
+
+#[test]
+pub fn test_modulus_does_not_overflow() {
+    // a^2 + b^2 would be infinite
+    let z: ComplexNumber = ComplexNumber::new(3e200, 4e200);
+    assert!((z.modulus() / 5e200 - 1.0).abs() < 1e-15);
+}
+
+#[test]
+pub fn test_polar() {
+    use std::f64::consts::FRAC_PI_2;
+
+    let z = ComplexNumber::from_polar(2.0, FRAC_PI_2);
+    assert_close(z, (0.0, 2.0));
+
+    let (r, theta) = ComplexNumber::new(1.0, 1.0).to_polar();
+    assert_close(ComplexNumber::from_polar(r, theta), (1.0, 1.0));
+    assert_eq!(theta, std::f64::consts::FRAC_PI_4);
+}
+
+#[test]
+pub fn test_exp_and_ln() {
+    use std::f64::consts::{E, PI};
+
+    // Euler's identity: e^(i pi) = -1
+    assert_close(ComplexNumber::new(0.0, PI).exp(), (-1.0, 0.0));
+    assert_close(ComplexNumber::new(1.0, 0.0).exp(), (E, 0.0));
+    assert_eq!(ComplexNumber::new(f64::INFINITY, 0.0).exp().to_tuple(), (f64::INFINITY, 0.0));
+
+    let z = ComplexNumber::new(1.5, -0.5);
+    assert_close(z.ln().exp(), z.to_tuple());
+    assert_close(ComplexNumber::new(0.0, 1.0).ln(), (0.0, PI / 2.0));
+    assert_close(ComplexNumber::new(100.0, 0.0).log(10.0), (2.0, 0.0));
+
+    // the branch cut on the negative real axis
+    assert_close(ComplexNumber::new(-1.0, 0.0).ln(), (0.0, PI));
+    assert_close(ComplexNumber::new(-1.0, -0.0).ln(), (0.0, -PI));
+}
+
+#[test]
+pub fn test_powers() {
+    let z = ComplexNumber::new(1.0, 1.0);
+
+    // (1 + i)^2 = 2i, (1 + i)^4 = -4
+    assert_eq!(z.powi(2).to_tuple(), (0.0, 2.0));
+    assert_eq!(z.powi(4).to_tuple(), (-4.0, 0.0));
+    assert_eq!(z.powi(0).to_tuple(), (1.0, 0.0));
+    assert_close(z.powi(-2), (0.0, -0.5));
+    // the base is inverted before squaring: z^2 would overflow
+    assert_eq!(ComplexNumber::new(1e200, 0.0).powi(-2).to_tuple(), (0.0, 0.0));
+    assert_close(ComplexNumber::new(1e200, 0.0).powi(-1) * 1e200, (1.0, 0.0));
+
+    assert_close(z.powf(2.0), (0.0, 2.0));
+    assert_close(ComplexNumber::new(-4.0, 0.0).powf(0.5), (0.0, 2.0));
+
+    // i^i = e^(-pi/2), a real number
+    let i = ComplexNumber::new(0.0, 1.0);
+    assert_close(i.powc(i), ((-std::f64::consts::FRAC_PI_2).exp(), 0.0));
+    assert_close(z.powc(ComplexNumber::from_real(3.0)), z.powi(3).to_tuple());
+    assert_eq!(ComplexNumber::default().powc(i).to_tuple(), (0.0, 0.0));
+    assert_eq!(ComplexNumber::default().powc(ComplexNumber::default()).to_tuple(), (1.0, 0.0));
+}
+
+#[test]
+pub fn test_sqrt() {
+    assert_eq!(ComplexNumber::new(-4.0, 0.0).sqrt().to_tuple(), (0.0, 2.0));
+    // the other side of the branch cut
+    assert_eq!(ComplexNumber::new(-4.0, -0.0).sqrt().to_tuple(), (0.0, -2.0));
+    assert_eq!(ComplexNumber::new(3.0, 4.0).sqrt().to_tuple(), (2.0, 1.0));
+    assert_eq!(ComplexNumber::new(-3.0, -4.0).sqrt().to_tuple(), (1.0, -2.0));
+    assert_eq!(ComplexNumber::new(0.0, 0.0).sqrt().to_tuple(), (0.0, 0.0));
+
+    let z = ComplexNumber::new(-2.5, 7.0);
+    let root = z.sqrt();
+    assert!(root.real() >= 0.0);
+    assert_close(root * root, z.to_tuple());
+    // near f64::MAX |z| + |a| overflows, the result doesn't
+    assert_close(ComplexNumber::new(1e308, 1e308).sqrt() / 1e153, (10.9868411346781, 4.550898605622273));
+    assert_close(ComplexNumber::new(-1e308, 1e308).sqrt() / 1e153, (4.550898605622273, 10.9868411346781));
+}
+
+#[test]
+pub fn test_trig_and_hyperbolic() {
+    let z = ComplexNumber::new(0.5, -1.25);
+
+    // sin^2 + cos^2 = 1, cosh^2 - sinh^2 = 1
+    let (s, c) = (z.sin(), z.cos());
+    assert_close(s * s + c * c, (1.0, 0.0));
+    let (sh, ch) = (z.sinh(), z.cosh());
+    assert_close(ch * ch - sh * sh, (1.0, 0.0));
+    assert_close(z.tan(), (s / c).to_tuple());
+    assert_close(z.tanh(), (sh / ch).to_tuple());
+
+    // sin(iy) = i sinh(y)
+    assert_close(ComplexNumber::new(0.0, 1.0).sin(), (0.0, 1f64.sinh()));
+    // tanh of a big number is 1, not NaN
+    assert_close(ComplexNumber::new(1000.0, 1.0).tanh(), (1.0, 0.0));
+    assert_close(ComplexNumber::new(1.0, -1000.0).tan(), (0.0, -1.0));
+}
+
+#[test]
+pub fn test_inverse_functions() {
+    let z = ComplexNumber::new(0.3, 0.8);
+
+    assert_close(z.asin().sin(), z.to_tuple());
+    assert_close(z.acos().cos(), z.to_tuple());
+    assert_close(z.atan().tan(), z.to_tuple());
+    assert_close(z.asinh().sinh(), z.to_tuple());
+    assert_close(z.acosh().cosh(), z.to_tuple());
+    assert_close(z.atanh().tanh(), z.to_tuple());
+
+    // real values inside the domain stay real
+    assert_close(ComplexNumber::new(0.5, 0.0).asin(), (0.5f64.asin(), 0.0));
+    assert_close(ComplexNumber::new(0.5, 0.0).acos(), (0.5f64.acos(), 0.0));
+    assert_close(ComplexNumber::new(2.0, 0.0).acosh(), (2f64.acosh(), 0.0));
+}
+
+#[test]
+pub fn test_branch_cuts() {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    let l = (2.0 + 3f64.sqrt()).ln();
+    let half_ln3 = 3f64.ln() / 2.0;
+
+    // asin, acos and atanh: the real axis beyond 1, above and below
+    assert_close(ComplexNumber::new(2.0, 0.0).asin(), (FRAC_PI_2, l));
+    assert_close(ComplexNumber::new(2.0, -0.0).asin(), (FRAC_PI_2, -l));
+    assert_close(ComplexNumber::new(2.0, 0.0).acos(), (0.0, -l));
+    assert_close(ComplexNumber::new(2.0, -0.0).acos(), (0.0, l));
+    assert_close(ComplexNumber::new(2.0, 0.0).atanh(), (half_ln3, FRAC_PI_2));
+    assert_close(ComplexNumber::new(2.0, -0.0).atanh(), (half_ln3, -FRAC_PI_2));
+
+    // asinh and atan: the imaginary axis beyond i, right and left
+    assert_close(ComplexNumber::new(0.0, 2.0).asinh(), (l, FRAC_PI_2));
+    assert_close(ComplexNumber::new(-0.0, 2.0).asinh(), (-l, FRAC_PI_2));
+    assert_close(ComplexNumber::new(0.0, 2.0).atan(), (FRAC_PI_2, half_ln3));
+    assert_close(ComplexNumber::new(-0.0, 2.0).atan(), (-FRAC_PI_2, half_ln3));
+
+    // acosh: the real axis below 1
+    assert_close(ComplexNumber::new(-2.0, 0.0).acosh(), (l, PI));
+    assert_close(ComplexNumber::new(-2.0, -0.0).acosh(), (l, -PI));
+    assert_close(ComplexNumber::new(0.0, 0.0).acosh(), (0.0, FRAC_PI_2));
+}
+
+#[test]
+pub fn test_functions_f32() {
+    use complex_numbers::solution::Complex32;
+
+    let z = Complex32::new(3.0, 4.0);
+    assert_eq!(z.modulus(), 5.0);
+    assert_eq!(z.sqrt().to_tuple(), (2.0, 1.0));
+    let w = z.ln().exp();
+    assert!((w - z).modulus() < 1e-5);
+}
+
+#[test]
+pub fn test_partial_ord_by_modulus() {
+    // < and > compare the modulus: |3| < |5i|, even if sort() puts (0, 5) first
+    let a = ComplexNumber::new(3.0, 0.0);
+    let b = ComplexNumber::new(0.0, 5.0);
+    assert!(a < b);
+    assert!(b > a);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Greater);
+    assert_eq!(ComplexNumber::new(f64::NAN, 0.0).partial_cmp(&a), None);
+
+    // for Gaussian integers they follow Ord
+    assert!(ComplexNumber::new(3, 0) > ComplexNumber::new(0, 5));
+}
@@ -0,0 +1,74 @@
+// compares slugify and is_slug with the original implementation, which scanned
+// SUBS_I for every non ascii char and always allocated a new String
+// run with: cargo bench
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+mod baseline {
+    const SUBS_I: &str = "àáâäæãåāăąçćčđďèéêëēėęěğǵḧîïíīįìıİłḿñńǹňôöòóœøōõőṕŕřßśšşșťțûüùúūǘůűųẃẍÿýžźż";
+    const SUBS_O: &str = "aaaaaaaaaacccddeeeeeeeegghiiiiiiiilmnnnnoooooooooprrsssssttuuuuuuuuuwxyyzzz";
+
+    fn conv(c: char) -> char {
+        match c {
+            'a'..='z' | '0'..='9' => c,
+            _ => {
+                for (i, x) in SUBS_I.chars().enumerate() {
+                    if x == c {
+                        return SUBS_O.as_bytes()[i] as char;
+                    }
+                }
+                '-'
+            }
+        }
+    }
+
+    pub fn slugify(s: &str) -> String {
+        let mut slug = String::new();
+        for c in s.chars() {
+            for c in c.to_lowercase() {
+                let c = conv(c);
+                if c != '-' {
+                    slug.push(c);
+                } else if !slug.ends_with('-') {
+                    slug.push('-');
+                }
+            }
+        }
+        if slug.ends_with('-') && slug.len() > 1 {
+            slug.pop();
+        }
+        slug
+    }
+
+    pub fn is_slug(s: &str) -> bool {
+        slugify(s) == s
+    }
+}
+
+const TITLE: &str = "Così è uno slug: perché žluťoučký kůň úpěl ďábelské ódy!";
+const SLUG: &str = "cosi-e-uno-slug-perche-zlutoucky-kun-upel-dabelske-ody";
+
+fn bench_slugify(c: &mut Criterion) {
+    let long = TITLE.repeat(100);
+
+    let mut group = c.benchmark_group("slugify");
+    group.bench_function("baseline/title", |b| b.iter(|| baseline::slugify(black_box(TITLE))));
+    group.bench_function("table/title", |b| b.iter(|| ff_slug::slugify(black_box(TITLE))));
+    group.bench_function("baseline/long", |b| b.iter(|| baseline::slugify(black_box(&long))));
+    group.bench_function("table/long", |b| b.iter(|| ff_slug::slugify(black_box(&long))));
+    group.bench_function("baseline/slug", |b| b.iter(|| baseline::slugify(black_box(SLUG))));
+    group.bench_function("table/slug", |b| b.iter(|| ff_slug::slugify(black_box(SLUG))));
+    group.finish();
+}
+
+fn bench_is_slug(c: &mut Criterion) {
+    let mut group = c.benchmark_group("is_slug");
+    group.bench_function("baseline/valid", |b| b.iter(|| baseline::is_slug(black_box(SLUG))));
+    group.bench_function("validate/valid", |b| b.iter(|| ff_slug::is_slug(black_box(SLUG))));
+    group.bench_function("baseline/invalid", |b| b.iter(|| baseline::is_slug(black_box(TITLE))));
+    group.bench_function("validate/invalid", |b| b.iter(|| ff_slug::is_slug(black_box(TITLE))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_slugify, bench_is_slug);
+criterion_main!(benches);
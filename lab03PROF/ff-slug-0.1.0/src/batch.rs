@@ -0,0 +1,198 @@
+// batch mode: many titles, one per line, are converted to slugs which are unique
+// among themselves and among a list of already existing slugs
+
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+
+use clap::ValueEnum;
+
+use crate::options::SlugOptions;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    /// one slug per line
+    Plain,
+    /// original<TAB>slug
+    Tsv,
+    /// array of {"original": ..., "slug": ...}
+    Json,
+}
+
+pub struct UniqueSlugs {
+    used: HashSet<String>,
+    separator: char,
+}
+
+impl UniqueSlugs {
+    pub fn new(separator: char) -> Self {
+        UniqueSlugs {
+            used: HashSet::new(),
+            separator,
+        }
+    }
+
+    // same separator used by the slugs
+    pub fn for_options(opts: &SlugOptions) -> Self {
+        Self::new(opts.separator)
+    }
+
+    // mark as used the slugs already taken elsewhere
+    pub fn preload<I: IntoIterator<Item = String>>(&mut self, slugs: I) {
+        self.used.extend(slugs);
+    }
+
+    // returns slug if it's still free, otherwise the first free slug-2, slug-3...
+    pub fn unique(&mut self, slug: String) -> String {
+        self.unique_with(slug, "")
+    }
+
+    // same as unique, but the counter goes between slug and suffix (e.g. an extension)
+    pub fn unique_with(&mut self, slug: String, suffix: &str) -> String {
+        let name = format!("{}{}", slug, suffix);
+        if !self.used.contains(&name) {
+            self.used.insert(name.clone());
+            return name;
+        }
+
+        let mut n = 2;
+        loop {
+            let candidate = format!("{}{}{}{}", slug, self.separator, n, suffix);
+            if !self.used.contains(&candidate) {
+                self.used.insert(candidate.clone());
+                return candidate;
+            }
+            n += 1;
+        }
+    }
+}
+
+// read the titles from input and write the slugs made by slugify to out,
+// empty lines are skipped; the titles without letters or digits would all become
+// "-" ("-", "--2"...), they are skipped too and returned, to be reported
+pub fn run<R, W, F>(input: R, out: &mut W, slugify: F, slugs: &mut UniqueSlugs, format: Format) -> io::Result<Vec<String>>
+where
+    R: BufRead,
+    W: Write,
+    F: Fn(&str) -> String,
+{
+    if format == Format::Json {
+        write!(out, "[")?;
+    }
+
+    let mut first = true;
+    let mut skipped = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        let title = line.trim();
+        if title.is_empty() {
+            continue;
+        }
+
+        let slug = slugify(title);
+        if !slug.chars().any(|c| c.is_ascii_alphanumeric()) {
+            skipped.push(title.to_string());
+            continue;
+        }
+        let slug = slugs.unique(slug);
+
+        match format {
+            Format::Plain => writeln!(out, "{}", slug)?,
+            // a tab in the title would add a column
+            Format::Tsv => writeln!(out, "{}\t{}", title.replace('\t', " "), slug)?,
+            Format::Json => {
+                let entry = serde_json::json!({ "original": title, "slug": slug });
+                write!(out, "{}\n  {}", if first { "" } else { "," }, entry)?;
+            }
+        }
+        first = false;
+    }
+
+    if format == Format::Json {
+        writeln!(out, "{}]", if first { "" } else { "\n" })?;
+    }
+    Ok(skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{slugify, slugify_path};
+
+    fn run_to_string(input: &str, slugs: &mut UniqueSlugs, format: Format) -> String {
+        let mut out = Vec::new();
+        let to_slug = |s: &str| slugify(s).into_owned();
+        run(input.as_bytes(), &mut out, to_slug, slugs, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn unique_adds_suffixes() {
+        let mut slugs = UniqueSlugs::new('-');
+        assert_eq!(slugs.unique("hello".to_string()), "hello");
+        assert_eq!(slugs.unique("hello".to_string()), "hello-2");
+        assert_eq!(slugs.unique("hello".to_string()), "hello-3");
+        assert_eq!(slugs.unique("world".to_string()), "world");
+    }
+
+    #[test]
+    fn unique_with_suffix() {
+        let mut slugs = UniqueSlugs::new('-');
+        assert_eq!(slugs.unique_with("photo".to_string(), ".jpg"), "photo.jpg");
+        assert_eq!(slugs.unique_with("photo".to_string(), ".jpg"), "photo-2.jpg");
+        assert_eq!(slugs.unique_with("photo".to_string(), ".png"), "photo.png");
+    }
+
+    #[test]
+    fn unique_with_preloaded_slugs() {
+        let mut slugs = UniqueSlugs::new('_');
+        slugs.preload(["hello".to_string(), "hello_2".to_string()]);
+        assert_eq!(slugs.unique("hello".to_string()), "hello_3");
+    }
+
+    #[test]
+    fn batch_plain() {
+        let mut slugs = UniqueSlugs::new('-');
+        let out = run_to_string("Hello World\n\nhello world!\n  Così è  \n", &mut slugs, Format::Plain);
+        assert_eq!(out, "hello-world\nhello-world-2\ncosi-e\n");
+    }
+
+    #[test]
+    fn batch_skips_titles_without_letters_or_digits() {
+        let mut slugs = UniqueSlugs::new('-');
+        let mut out = Vec::new();
+        let to_slug = |s: &str| slugify(s).into_owned();
+        let skipped = run("!!!\nHello\n!!!\n¿?\n".as_bytes(), &mut out, to_slug, &mut slugs, Format::Plain).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "hello\n");
+        assert_eq!(skipped, vec!["!!!", "!!!", "¿?"]);
+    }
+
+    #[test]
+    fn batch_paths() {
+        let mut slugs = UniqueSlugs::new('-');
+        let mut out = Vec::new();
+        let opts = SlugOptions::default();
+        let input = "Shop/Caffè\nShop/Caffe\n";
+        run(input.as_bytes(), &mut out, |s| slugify_path(s, &opts), &mut slugs, Format::Plain).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "shop/caffe\nshop/caffe-2\n");
+    }
+
+    #[test]
+    fn batch_tsv() {
+        let mut slugs = UniqueSlugs::new('-');
+        let out = run_to_string("Hello\tWorld\nHello World\n", &mut slugs, Format::Tsv);
+        assert_eq!(out, "Hello World\thello-world\nHello World\thello-world-2\n");
+    }
+
+    #[test]
+    fn batch_json() {
+        let mut slugs = UniqueSlugs::new('-');
+        let out = run_to_string("Say \"hi\"\nSay hi\n", &mut slugs, Format::Json);
+        let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(v[0]["original"], "Say \"hi\"");
+        assert_eq!(v[0]["slug"], "say-hi");
+        assert_eq!(v[1]["slug"], "say-hi-2");
+
+        let out = run_to_string("", &mut slugs, Format::Json);
+        assert_eq!(out, "[]\n");
+    }
+}
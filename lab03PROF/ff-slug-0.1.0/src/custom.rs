@@ -0,0 +1,169 @@
+// project specific substitutions loaded from a TOML or JSON file, e.g.
+//
+//     "&" = " and "
+//     "€" = "euro "
+//     "C++" = "cpp"
+//
+// keys can be single chars or longer sequences and are matched ignoring case,
+// the longest key wins; replacements are inserted as they are and then slugified,
+// so put spaces around them to make them separate words
+// they are checked before the locale and the built-in tables
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum TableError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    UnknownFormat(PathBuf),
+    EmptyKey,
+    // two keys which differ only by case, keys are matched ignoring it
+    DuplicateKey(String, String),
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableError::Io(e) => write!(f, "cannot read table: {}", e),
+            TableError::Toml(e) => write!(f, "invalid TOML table: {}", e),
+            TableError::Json(e) => write!(f, "invalid JSON table: {}", e),
+            TableError::UnknownFormat(p) => {
+                write!(f, "{}: unknown table format, use .toml or .json", p.display())
+            }
+            TableError::EmptyKey => write!(f, "empty key in table"),
+            TableError::DuplicateKey(a, b) => {
+                write!(f, "keys {:?} and {:?} differ only by case", a, b)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+#[derive(Debug, Clone, Default)]
+pub struct CustomTable {
+    // (key, replacement) with the keys lowercase, the longest first: the first
+    // key matching is the longest one
+    entries: Vec<(String, String)>,
+}
+
+impl CustomTable {
+    pub fn new(entries: HashMap<String, String>) -> Result<Self, TableError> {
+        // lowercase key => the key as written, to report collisions
+        let mut keys = HashMap::new();
+        let mut table = CustomTable::default();
+        for (k, v) in entries {
+            if k.is_empty() {
+                return Err(TableError::EmptyKey);
+            }
+            let lower = k.to_lowercase();
+            if let Some(other) = keys.insert(lower.clone(), k.clone()) {
+                // in the same order whatever the order of the map
+                let (a, b) = if other < k { (other, k) } else { (k, other) };
+                return Err(TableError::DuplicateKey(a, b));
+            }
+            table.entries.push((lower, v));
+        }
+        // two keys of the same length can't both match
+        table.entries.sort_by_key(|(k, _)| Reverse(k.chars().count()));
+        Ok(table)
+    }
+
+    pub fn from_toml(s: &str) -> Result<Self, TableError> {
+        Self::new(toml::from_str(s).map_err(TableError::Toml)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, TableError> {
+        Self::new(serde_json::from_str(s).map_err(TableError::Json)?)
+    }
+
+    // the format is chosen by the extension of the file
+    pub fn load(path: &Path) -> Result<Self, TableError> {
+        let parse = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml,
+            Some("json") => Self::from_json,
+            _ => return Err(TableError::UnknownFormat(path.to_path_buf())),
+        };
+        let contents = std::fs::read_to_string(path).map_err(TableError::Io)?;
+        parse(&contents)
+    }
+
+    // the longest key at the start of s: returns its length in bytes and the replacement
+    pub fn longest_match(&self, s: &str) -> Option<(usize, &str)> {
+        self.entries
+            .iter()
+            .find_map(|(k, r)| prefix_len(s, k).map(|len| (len, r.as_str())))
+    }
+}
+
+// the length in bytes of the start of s which, lowercase, is key (lowercase too),
+// compared char by char without building the lowercase string
+fn prefix_len(s: &str, key: &str) -> Option<usize> {
+    let mut key = key.chars();
+    for (i, c) in s.char_indices() {
+        for l in c.to_lowercase() {
+            if key.next() != Some(l) {
+                return None;
+            }
+        }
+        if key.as_str().is_empty() {
+            return Some(i + c.len_utf8());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_toml() {
+        let t = CustomTable::from_toml("\"&\" = \" and \"\n\"C++\" = \"cpp\"\n").unwrap();
+        assert_eq!(t.longest_match("& co"), Some((1, " and ")));
+        assert_eq!(t.longest_match("c++ rocks"), Some((3, "cpp")));
+        assert_eq!(t.longest_match("c+"), None);
+    }
+
+    #[test]
+    fn from_json() {
+        let t = CustomTable::from_json(r#"{"€": "euro ", "%": " percent"}"#).unwrap();
+        assert_eq!(t.longest_match("€5"), Some(("€".len(), "euro ")));
+        assert_eq!(t.longest_match("5%"), None);
+    }
+
+    #[test]
+    fn longest_key_wins() {
+        let t = CustomTable::from_json(r#"{"a": "1", "ab": "2", "abc": "3"}"#).unwrap();
+        assert_eq!(t.longest_match("abcd"), Some((3, "3")));
+        assert_eq!(t.longest_match("abd"), Some((2, "2")));
+        assert_eq!(t.longest_match("ad"), Some((1, "1")));
+    }
+
+    #[test]
+    fn keys_ignore_case() {
+        let t = CustomTable::from_json(r#"{"C++": "cpp", "straße": "strasse"}"#).unwrap();
+        assert_eq!(t.longest_match("c++"), Some((3, "cpp")));
+        assert_eq!(t.longest_match("STRAßE 1"), Some(("STRAßE".len(), "strasse")));
+        assert_eq!(t.longest_match("STRASSE"), None);
+        assert!(matches!(
+            CustomTable::from_json(r#"{"Ab": "1", "aB": "2"}"#),
+            Err(TableError::DuplicateKey(a, b)) if a == "Ab" && b == "aB"
+        ));
+    }
+
+    #[test]
+    fn invalid_tables() {
+        assert!(matches!(CustomTable::from_json(r#"{"": "x"}"#), Err(TableError::EmptyKey)));
+        assert!(matches!(CustomTable::from_json("[1, 2]"), Err(TableError::Json(_))));
+        assert!(matches!(CustomTable::from_toml("x = 1"), Err(TableError::Toml(_))));
+        assert!(matches!(
+            CustomTable::load(Path::new("table.yaml")),
+            Err(TableError::UnknownFormat(_))
+        ));
+    }
+}
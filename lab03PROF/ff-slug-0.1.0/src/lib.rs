@@ -0,0 +1,479 @@
+pub mod batch;
+pub mod custom;
+pub mod locale;
+pub mod options;
+pub mod path;
+pub mod rename;
+pub mod slug;
+mod translit;
+
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+pub use custom::{CustomTable, TableError};
+pub use locale::Locale;
+pub use options::SlugOptions;
+pub use path::{is_slug_path, slugify_path};
+pub use slug::{Slug, SlugError};
+
+const SUBS_I: &str = "àáâäæãåāăąçćčđďèéêëēėęěğǵḧîïíīįìıİłḿñńǹňôöòóœøōõőṕŕřßśšşșťțûüùúūǘůűųẃẍÿýžźż";
+const SUBS_O: &str = "aaaaaaaaaacccddeeeeeeeegghiiiiiiiilmnnnnoooooooooprrsssssttuuuuuuuuuwxyyzzz";
+
+
+// with ieratators (not used, supplied for example)
+fn _conv_alt(c: char) -> char {
+    match c {
+        'a'..='z' | '0'..='9' => c,
+        _ => match SUBS_I.chars().position(|x| x == c) {
+            Some(i) => SUBS_O.chars().nth(i).unwrap(),
+            None => '-',
+        }
+    }
+}
+
+// SUBS_I chars are all in the Latin-1 Supplement, Latin Extended-A/B and Latin
+// Extended Additional blocks: a table indexed by code point replaces the scan of SUBS_I
+const LATIN: std::ops::Range<u32> = 0xC0..0x250;
+const LATIN_ADDITIONAL: std::ops::Range<u32> = 0x1E00..0x1F00;
+const CONV_SIZE: usize = (LATIN.end - LATIN.start + LATIN_ADDITIONAL.end - LATIN_ADDITIONAL.start) as usize;
+
+static CONV_TABLE: OnceLock<[u8; CONV_SIZE]> = OnceLock::new();
+
+fn conv_index(c: char) -> Option<usize> {
+    let code = c as u32;
+    if LATIN.contains(&code) {
+        Some((code - LATIN.start) as usize)
+    } else if LATIN_ADDITIONAL.contains(&code) {
+        Some((code - LATIN_ADDITIONAL.start + LATIN.end - LATIN.start) as usize)
+    } else {
+        None
+    }
+}
+
+// built once, on first use; 0 means no conversion
+fn conv_table() -> &'static [u8; CONV_SIZE] {
+    CONV_TABLE.get_or_init(|| {
+        let mut table = [0; CONV_SIZE];
+        // in SUBS_O all chars are 1 byte long, therefore we can zip with its bytes
+        for (x, o) in SUBS_I.chars().zip(SUBS_O.bytes()) {
+            let i = conv_index(x).expect("SUBS_I char outside the conv table");
+            table[i] = o;
+        }
+        table
+    })
+}
+
+fn conv(c: char) -> char {
+    match c {
+        // use match syntax to match a range of characters
+        'a'..='z' | '0'..='9' => c,
+        _ => match conv_index(c).map(|i| conv_table()[i]) {
+            Some(o) if o != 0 => o as char,
+            _ => '-',
+        },
+    }
+}
+
+// when s is already a slug it's returned as it is, without allocating
+pub fn slugify(s: &str) -> Cow<'_, str> {
+    slugify_with(s, &SlugOptions::default())
+}
+
+pub fn slugify_with<'a>(s: &'a str, opts: &SlugOptions) -> Cow<'a, str> {
+    if opts.keeps_slugs(s) && is_slug(s) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut slug = String::with_capacity(s.len());
+
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        // a custom substitution replaces the longest matching sequence,
+        // its replacement is slugified as if it were in the input
+        if let Some((len, r)) = opts.custom.as_ref().and_then(|t| t.longest_match(rest)) {
+            r.chars().for_each(|c| push_input(&mut slug, c, opts));
+            rest = &rest[len..];
+        } else {
+            push_input(&mut slug, c, opts);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if slug.ends_with('-') && slug.len() > 1 {
+        slug.pop();
+    }
+
+    if opts.keeps_words() {
+        return Cow::Owned(slug);
+    }
+
+    // now the slug is made of words separated by '-': drop the stop words,
+    // join them with the separator and stop at the last word fitting max_len
+    let mut res = String::with_capacity(slug.len());
+    let words = slug.split('-').filter(|w| !opts.is_stop_word(w));
+    for (i, w) in words.enumerate() {
+        let sep_len = if i == 0 { 0 } else { opts.separator.len_utf8() };
+        if let Some(max) = opts.max_len {
+            if res.len() + sep_len + w.len() > max {
+                // a single word longer than max_len can only be cut
+                if i == 0 {
+                    res.push_str(&w[..max]);
+                }
+                break;
+            }
+        }
+        if i > 0 {
+            res.push(opts.separator);
+        }
+        res.push_str(w);
+    }
+    Cow::Owned(res)
+}
+
+// lowercase an input char, convert it and push the result
+fn push_input(slug: &mut String, c: char, opts: &SlugOptions) {
+    let upper = opts.preserve_case && c.is_uppercase();
+
+    // converting a char to lowercase may lead to one or more chars
+    // we need to to loop on them
+    // we could have used c.to_lowercase().chars() but it could be slightly less efficient 
+
+    match opts.locale.and_then(|l| l.to_lowercase(c)) {
+        Some(c) => push_lower(slug, c, upper, opts),
+        // ascii chars have a single lowercase char, no need for the iterator
+        None if c.is_ascii() => push_lower(slug, c.to_ascii_lowercase(), upper, opts),
+        None => {
+            for c in c.to_lowercase() {
+                push_lower(slug, c, upper, opts);
+            }
+        }
+    }
+}
+
+// convert a lowercase char and push the result
+fn push_lower(slug: &mut String, c: char, upper: bool, opts: &SlugOptions) {
+    let start = slug.len();
+
+    // the locale overrides come first, then the default mapping
+    if let Some(s) = opts.locale.and_then(|l| l.translit(c)) {
+        s.chars().for_each(|t| push_char(slug, conv(t)));
+    } else {
+        let x = conv(c);
+        if x != '-' {
+            push_char(slug, x);
+        } else if !translit::translit(c, |t| push_char(slug, conv(t))) {
+            // not even in the transliteration tables: it's a separator
+            push_char(slug, '-');
+        }
+    }
+
+    // the output is ascii, so we can restore the case of the first char
+    // pushed (e.g. Ж => Zh)
+    if upper && slug.len() > start {
+        slug[start..start + 1].make_ascii_uppercase();
+    }
+}
+
+// shortcut for slugify_with() when only the locale is needed
+pub fn slugify_locale(s: &str, locale: Locale) -> String {
+    slugify_with(s, &SlugOptions::new().locale(locale)).into_owned()
+}
+
+// push a converted char, collapsing consecutive separators
+fn push_char(slug: &mut String, c: char) {
+    if c != '-' {
+        slug.push(c);
+    } else if !slug.ends_with('-') {
+        slug.push('-');
+    }
+}
+
+// a valid slug is lowercase, made of ascii letters and digits, and has single
+// separators between words (see slug::validate)
+pub fn is_slug(s: &str) -> bool {
+    slug::validate(s).is_ok()
+}
+
+pub trait MySlug {
+    fn is_slug(&self) -> bool;
+    fn to_slug(&self) -> String;
+    fn to_slug_with(&self, opts: &SlugOptions) -> String;
+}
+
+// naive implementation of the trait for &str and String
+//impl MySlug for &str {
+//    fn is_slug(&self) -> bool {
+//        is_slug(self)
+//    }
+//
+//    fn to_slug(&self) -> String {
+//        slugify(self)
+//    }
+//}
+//
+//impl MySlug for String {
+//    fn is_slug(&self) -> bool {
+//        is_slug(self)
+//    }
+//
+//    fn to_slug(&self) -> String {
+//        slugify(self)
+//    }
+//    
+//}
+
+
+//generic implementation of the trait for all types that implement AsRef<str>
+impl<T: AsRef<str>> MySlug for T {
+    fn is_slug(&self) -> bool {
+        is_slug(self.as_ref())
+    }
+
+    fn to_slug(&self) -> String {
+        slugify(self.as_ref()).into_owned()
+    }
+
+    fn to_slug_with(&self, opts: &SlugOptions) -> String {
+        slugify_with(self.as_ref(), opts).into_owned()
+    }
+}
+
+
+#[allow(dead_code)]
+fn demo_slug_trait() {
+    let s1 = "Not a slug";
+    let s2 = String::from("this-is-a-slug");
+    println!("{} is not a slug: {}", s1, s1.is_slug());
+    println!("{} is a slug: {}", s2, s2.is_slug());
+
+    println!("Conv to slug for &str: {} => {}", s1, s1.to_slug());
+    println!("Conv to slug for String: {} => {}", s2, s2.to_slug());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_char_with_accent() {
+        assert_eq!(super::slugify(&"ò"), "o");
+    }
+
+    #[test]
+    fn single_char_without_accent() {
+        assert_eq!(super::slugify(&"x"), "x");
+    }
+
+    #[test]
+    fn single_invalid_char() {
+        assert_eq!(super::slugify(&"/"), "-");
+    }
+
+    #[test]
+    fn single_unknown_accent() {
+        assert_eq!(super::slugify("☃"), "-");
+    }
+
+    #[test]
+    fn greek_accent() {
+        assert_eq!(super::slugify("ῶ"), "o");
+    }
+
+    #[test]
+    fn cyrillic() {
+        assert_eq!(super::slugify("Привет мир"), "privet-mir");
+    }
+
+    #[test]
+    fn vietnamese_and_ligatures() {
+        assert_eq!(super::slugify("Tiếng Việt ﬁne"), "tieng-viet-fine");
+    }
+
+    #[test]
+    fn decomposed_accents() {
+        assert_eq!(super::slugify("Cafe\u{301} ok"), "cafe-ok");
+    }
+
+    #[test]
+    fn hebrew_and_arabic() {
+        assert_eq!(super::slugify("שלום"), "shlvm");
+        assert_eq!(super::slugify("مرحبا بالعالم"), "mrhba-balalm");
+    }
+
+    #[test]
+    fn cjk() {
+        assert_eq!(super::slugify("北京 中国人"), "bei-jing-zhong-guo-ren");
+        assert_eq!(super::slugify("すし"), "sushi");
+        assert_eq!(super::slugify("한국어"), "hangukeo");
+    }
+
+    #[test]
+    fn multiple_words() {
+        assert_eq!(super::slugify(&"Hello World"), "hello-world");
+    }
+    #[test]
+    fn multiple_words_with_accents() {
+        assert_eq!(super::slugify(&"Così è uno slug!"), "cosi-e-uno-slug");
+    }
+
+    #[test]
+    fn test_empty_string() {
+        assert_eq!(super::slugify(&""), "");
+    }
+
+    #[test]
+    fn multiple_spaces() {
+        assert_eq!(super::slugify(&"Hello   World!!"), "hello-world");
+    }
+
+    #[test]
+    fn only_special_chars() {
+        assert_eq!(super::slugify(&"!@#$%^&*()"), "-");
+    }
+
+    #[test]
+    fn multiple_spaces_at_the_end() {
+        assert_eq!(super::slugify(&"Hello  World!!  "), "hello-world");
+    }
+
+    #[test]
+    fn multiple_invalid_chars_at_the_end() {
+        assert_eq!(super::slugify(&"Hello  World/\\"), "hello-world");
+    }
+
+    #[test]
+    fn options_separator() {
+        let opts = SlugOptions::new().separator('_');
+        assert_eq!(slugify_with("Hello World!", &opts), "hello_world");
+        let opts = SlugOptions::new().separator('.');
+        assert_eq!(slugify_with("v1 2 3", &opts), "v1.2.3");
+    }
+
+    #[test]
+    fn options_max_len() {
+        let opts = SlugOptions::new().max_len(12);
+        assert_eq!(slugify_with("Hello beautiful World", &opts), "hello");
+        assert_eq!(slugify_with("Hello big World", &opts), "hello-big");
+        assert_eq!(slugify_with("Supercalifragilistic", &opts), "supercalifra");
+        assert_eq!(slugify_with("Hello", &opts), "hello");
+    }
+
+    #[test]
+    fn options_preserve_case() {
+        let opts = SlugOptions::new().preserve_case(true);
+        assert_eq!(slugify_with("Così È uno Slug!", &opts), "Cosi-E-uno-Slug");
+        assert_eq!(slugify_with("Жуков", &opts), "Zhukov");
+    }
+
+    #[test]
+    fn options_stop_words() {
+        let opts = SlugOptions::new().remove_stop_words();
+        assert_eq!(slugify_with("The Lord of the Rings", &opts), "lord-rings");
+        assert_eq!(slugify_with("Il nome della rosa", &opts), "nome-della-rosa");
+        let opts = SlugOptions::new().stop_words(["della"]).preserve_case(true);
+        assert_eq!(slugify_with("Il nome Della rosa", &opts), "Il-nome-rosa");
+    }
+
+    #[test]
+    fn options_default_is_slugify() {
+        let opts = SlugOptions::default();
+        for s in ["", "/", "Hello  World!!  ", "Così è uno slug!"] {
+            assert_eq!(slugify_with(s, &opts), slugify(s));
+        }
+    }
+
+    #[test]
+    fn locale_german() {
+        assert_eq!(slugify_locale("Müller Straße", Locale::German), "mueller-strasse");
+        assert_eq!(slugify("Müller Straße"), "muller-strase");
+    }
+
+    #[test]
+    fn locale_danish() {
+        assert_eq!(slugify_locale("Søren Kierkegård", Locale::Danish), "soeren-kierkegaard");
+        assert_eq!(slugify_locale("Æble", Locale::Danish), "aeble");
+    }
+
+    #[test]
+    fn locale_turkish() {
+        assert_eq!(slugify_locale("DİYARBAKIR", Locale::Turkish), "diyarbakir");
+        let opts = SlugOptions::new().locale(Locale::Turkish).preserve_case(true);
+        assert_eq!(slugify_with("İstanbul Işık", &opts), "Istanbul-Isik");
+    }
+
+    #[test]
+    fn locale_italian() {
+        assert_eq!(slugify_locale("L'amico dell’uomo", Locale::Italian), "lamico-delluomo");
+        assert_eq!(slugify("L'amico"), "l-amico");
+    }
+
+    #[test]
+    fn locale_falls_back_to_default() {
+        assert_eq!(slugify_locale("Così è Привет", Locale::German), "cosi-e-privet");
+    }
+
+    #[test]
+    fn custom_table() {
+        let table = CustomTable::from_json(r#"{"&": " and ", "€": " euro ", "C++": "cpp", "ä": "ae"}"#).unwrap();
+        let opts = SlugOptions::new().custom_table(table);
+        assert_eq!(slugify_with("Tom & Jerry", &opts), "tom-and-jerry");
+        assert_eq!(slugify_with("Prezzo: 10€", &opts), "prezzo-10-euro");
+        assert_eq!(slugify_with("C++ vs C+", &opts), "cpp-vs-c");
+        assert_eq!(slugify_with("Bär", &opts), "baer");
+    }
+
+    #[test]
+    fn custom_table_over_locale() {
+        let table = CustomTable::from_json(r#"{"ß": "sz", "l'": "lo "}"#).unwrap();
+        let opts = SlugOptions::new().custom_table(table).locale(Locale::German);
+        assert_eq!(slugify_with("Straße für l'uomo", &opts), "strasze-fuer-lo-uomo");
+    }
+
+    #[test]
+    fn trait_with_options() {
+        let opts = SlugOptions::new().separator('_').max_len(11);
+        assert_eq!("Hello big World".to_slug_with(&opts), "hello_big");
+    }
+
+    #[test]
+    fn trait_for_string() {
+        let s = String::from("Hello World");
+        assert_eq!(s.to_slug(), "hello-world");
+        assert_eq!(s.is_slug(), false);
+    }
+
+    #[test]
+    fn trait_for_str() {
+        let s = "Hello World";
+        assert_eq!(s.to_slug(), "hello-world");
+        assert_eq!(s.is_slug(), false);
+    }
+
+    #[test]
+    fn conv_table_covers_subs() {
+        for (x, o) in SUBS_I.chars().zip(SUBS_O.chars()) {
+            assert_eq!(conv(x), o);
+        }
+        assert_eq!(conv('ῶ'), '-');
+        assert_eq!(conv('A'), '-');
+    }
+
+    #[test]
+    fn borrowed_when_already_a_slug() {
+        assert!(matches!(slugify("hello-world"), Cow::Borrowed("hello-world")));
+        assert!(matches!(slugify("Hello-world"), Cow::Owned(_)));
+        let opts = SlugOptions::new().separator('_');
+        assert_eq!(slugify_with("hello-world", &opts), "hello_world");
+        let opts = SlugOptions::new().max_len(5);
+        assert_eq!(slugify_with("hello-world", &opts), "hello");
+        let opts = SlugOptions::new().locale(Locale::German).max_len(20);
+        assert!(matches!(slugify_with("hello-world", &opts), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn is_slug_validates() {
+        assert!(is_slug("hello-world"));
+        assert!(!is_slug("-"));
+        assert!(!is_slug(""));
+        assert!(!is_slug("hello_world"));
+    }
+}
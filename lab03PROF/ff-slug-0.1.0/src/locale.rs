@@ -0,0 +1,54 @@
+// per-language rules, layered on top of conv() and the transliteration tables:
+// the overrides of the locale are checked first, everything else falls back to
+// the default mapping
+
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Locale {
+    #[value(name = "de")]
+    German,
+    #[value(name = "da")]
+    Danish,
+    #[value(name = "tr")]
+    Turkish,
+    #[value(name = "it")]
+    Italian,
+}
+
+const GERMAN: &[(char, &str)] = &[('ä', "ae"), ('ö', "oe"), ('ü', "ue"), ('ß', "ss")];
+
+const DANISH: &[(char, &str)] = &[('æ', "ae"), ('ø', "oe"), ('å', "aa")];
+
+// apostrophes join the words instead of separating them: l'amico => lamico
+const ITALIAN: &[(char, &str)] = &[('\'', ""), ('’', "")];
+
+impl Locale {
+    fn overrides(self) -> &'static [(char, &'static str)] {
+        match self {
+            Locale::German => GERMAN,
+            Locale::Danish => DANISH,
+            // Turkish only changes how letters are lowercased
+            Locale::Turkish => &[],
+            Locale::Italian => ITALIAN,
+        }
+    }
+
+    // the replacement for a lowercase char, if the locale has its own
+    pub fn translit(self, c: char) -> Option<&'static str> {
+        self.overrides()
+            .iter()
+            .find(|&&(x, _)| x == c)
+            .map(|&(_, s)| s)
+    }
+
+    // lowercase chars which differ from the default Unicode mapping
+    // in Turkish I is the uppercase of the dotless ı and İ the uppercase of i
+    pub fn to_lowercase(self, c: char) -> Option<char> {
+        match (self, c) {
+            (Locale::Turkish, 'I') => Some('ı'),
+            (Locale::Turkish, 'İ') => Some('i'),
+            _ => None,
+        }
+    }
+}
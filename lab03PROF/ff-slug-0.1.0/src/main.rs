@@ -1,221 +1,201 @@
-use clap::Parser;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
-const SUBS_I: &str = "àáâäæãåāăąçćčđďèéêëēėęěğǵḧîïíīįìıİłḿñńǹňôöòóœøōõőṕŕřßśšşșťțûüùúūǘůűųẃẍÿýžźż";
-const SUBS_O: &str = "aaaaaaaaaacccddeeeeeeeegghiiiiiiiilmnnnnoooooooooprrsssssttuuuuuuuuuwxyyzzz";
+use clap::{Parser, Subcommand};
 
+use ff_slug::batch::{self, Format, UniqueSlugs};
+use ff_slug::rename;
+use ff_slug::{slugify_path, slugify_with, CustomTable, Locale, SlugOptions, TableError};
 
-// with ieratators (not used, supplied for example)
-fn _conv_alt(c: char) -> char {
-    match c {
-        'a'..='z' | '0'..='9' => c,
-        _ => match SUBS_I.chars().position(|x| x == c) {
-            Some(i) => SUBS_O.chars().nth(i).unwrap(),
-            None => '-',
-        }
-    }
-}
-
-// with for loop
-fn conv(c: char) -> char {
-    match c {
-        // use match syntax to match a range of characters
-        'a'..='z' | '0'..='9' => c,
-        _ => {
-            // we can either ierate over the characters of SUBS_I or convert it to a vector of chars
-            for (i, x) in SUBS_I.chars().enumerate() {
-                if x == c {
-                    // we can't index a string but we can index a slice of bytes
-                    // in SUBS_O all chars are 1 byte long, therefore we can use as_bytes()
-                    // and get the i-th byte as a char
-                    return SUBS_O.as_bytes()[i] as char;
-                }
-            }
-            '-'
-        }
-    }
-}
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
 
-fn slugify(s: &str) -> String {
-    let mut slug = String::new();
+    /// string to slugify; if missing, titles are read one per line from --file or stdin
+    input: Option<String>,
 
-    for c in s.chars() {
-        // converting a char to lowercase may lead to one or more chars
-        // we need to to loop on them
-        // we could have used c.to_lowercase().chars() but it could be slightly less efficient 
+    #[arg(short, long, default_value = "false", global = true)]
+    verbose: bool,
 
-        for c in c.to_lowercase() {
-            let c = conv(c);
-            if c != '-' {
-                slug.push(c);
-            } else if !slug.ends_with('-') {
-                slug.push('-');
-            }
-        }
-    }
-    if slug.ends_with('-') && slug.len() > 1 {
-        slug.pop();
-    }
-    slug
-}
+    /// read the titles from a file instead of stdin
+    #[arg(short, long)]
+    file: Option<PathBuf>,
 
-fn is_slug(s: &str) -> bool {
-    slugify(s) == s
-}
+    /// file with the slugs already in use, one per line
+    #[arg(short, long)]
+    existing: Option<PathBuf>,
 
-trait MySlug {
-    fn is_slug(&self) -> bool;
-    fn to_slug(&self) -> String;
-}
+    /// output format in batch mode
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
 
-// naive implementation of the trait for &str and String
-//impl MySlug for &str {
-//    fn is_slug(&self) -> bool {
-//        is_slug(self)
-//    }
-//
-//    fn to_slug(&self) -> String {
-//        slugify(self)
-//    }
-//}
-//
-//impl MySlug for String {
-//    fn is_slug(&self) -> bool {
-//        is_slug(self)
-//    }
-//
-//    fn to_slug(&self) -> String {
-//        slugify(self)
-//    }
-//    
-//}
-
-
-//generic implementation of the trait for all types that implement AsRef<str>
-impl<T: AsRef<str>> MySlug for T {
-    fn is_slug(&self) -> bool {
-        is_slug(self.as_ref())
-    }
+    /// separator between words
+    #[arg(short, long, default_value = "-", global = true)]
+    separator: char,
 
-    fn to_slug(&self) -> String {
-        slugify(self.as_ref())
-    }
-}
+    /// maximum length, the slug is truncated at a word boundary
+    #[arg(short, long, global = true)]
+    max_len: Option<usize>,
 
+    /// keep uppercase letters
+    #[arg(short, long, default_value = "false", global = true)]
+    preserve_case: bool,
 
-#[allow(dead_code)]
-fn demo_slug_trait() {
-    let s1 = "Not a slug";
-    let s2 = String::from("this-is-a-slug");
-    println!("{} is not a slug: {}", s1, s1.is_slug());
-    println!("{} is a slug: {}", s2, s2.is_slug());
+    /// remove common words (a, the, di, il...)
+    #[arg(long, default_value = "false", global = true)]
+    stop_words: bool,
 
-    println!("Conv to slug for &str: {} => {}", s1, s1.to_slug());
-    println!("Conv to slug for String: {} => {}", s2, s2.to_slug());
-}
+    /// language specific transliteration rules
+    #[arg(short, long, value_enum, global = true)]
+    locale: Option<Locale>,
 
-#[derive(Debug, Parser)]
-struct Args {
-    input: String,
+    /// TOML or JSON file with custom substitutions, applied before the built-in ones
+    #[arg(short, long, global = true)]
+    table: Option<PathBuf>,
 
-    #[arg(short, long, default_value = "false")]
-    verbose: bool,
+    /// treat the input as a path and slugify each '/' segment
+    #[arg(long, default_value = "false")]
+    path: bool,
 
-    #[arg(short, long)]
-    repeat: Option<u32>,
+    /// with --path, keep only the first segments
+    #[arg(long, requires = "path")]
+    max_depth: Option<usize>,
 }
 
-fn main() {
-    let args: Args = Args::parse();
-
-    let res = slugify(&args.input);
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// rename the entries of a directory to their slugs, keeping the extensions
+    Rename {
+        dir: PathBuf,
+
+        /// only print what would be renamed
+        #[arg(short = 'n', long, default_value = "false")]
+        dry_run: bool,
+
+        /// rename the content of the subdirectories too
+        #[arg(short, long, default_value = "false")]
+        recursive: bool,
+
+        /// file recording the renames, to revert them with undo; appended to if it exists
+        #[arg(long, default_value = "ff-slug-undo.jsonl")]
+        manifest: PathBuf,
+    },
+    /// revert the renames recorded in a manifest
+    Undo { manifest: PathBuf },
+}
 
-    match args.repeat {
-        Some(n) => {
-            for i in 0..n {
-                if args.verbose {
-                    println!("[{}] {} => {}", i, args.input, res);
-                } else {
-                    println!("{}", res);
-                }
-            }
+impl Args {
+    fn options(&self) -> Result<SlugOptions, TableError> {
+        let mut opts = SlugOptions::new()
+            .separator(self.separator)
+            .preserve_case(self.preserve_case);
+        if let Some(max) = self.max_len {
+            opts = opts.max_len(max);
+        }
+        if self.stop_words {
+            opts = opts.remove_stop_words();
         }
-        None => if args.verbose {
-            println!("{} => {}", args.input, res)
+        if let Some(locale) = self.locale {
+            opts = opts.locale(locale);
+        }
+        if let Some(path) = &self.table {
+            opts = opts.custom_table(CustomTable::load(path)?);
+        }
+        if let Some(depth) = self.max_depth {
+            opts = opts.max_depth(depth);
+        }
+        Ok(opts)
+    }
+
+    fn slugify(&self, s: &str, opts: &SlugOptions) -> String {
+        if self.path {
+            slugify_path(s, opts)
         } else {
-            println!("{}", res)
+            slugify_with(s, opts).into_owned()
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn single_char_with_accent() {
-        assert_eq!(super::slugify(&"ò"), "o");
+fn batch(args: &Args, opts: &SlugOptions) -> io::Result<()> {
+    let mut slugs = UniqueSlugs::for_options(opts);
+    if let Some(path) = &args.existing {
+        let existing = std::fs::read_to_string(path)?;
+        slugs.preload(existing.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from));
     }
 
-    #[test]
-    fn single_char_without_accent() {
-        assert_eq!(super::slugify(&"x"), "x");
-    }
+    let input: Box<dyn BufRead> = match &args.file {
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(io::stdin().lock()),
+    };
 
-    #[test]
-    fn single_invalid_char() {
-        assert_eq!(super::slugify(&"/"), "-");
+    let slugify = |s: &str| args.slugify(s, opts);
+    let skipped = batch::run(input, &mut io::stdout().lock(), slugify, &mut slugs, args.format)?;
+    for title in skipped {
+        eprintln!("skipped: nothing to slugify in {:?}", title);
     }
+    Ok(())
+}
 
-    #[test]
-    fn single_unknown_accent() {
-        assert_eq!(super::slugify(&"ῶ"), "-");
-    }
+fn rename(args: &Args, opts: &SlugOptions, dir: &Path, dry_run: bool, recursive: bool, manifest: &Path) -> io::Result<()> {
+    // absolute paths in the manifest, so undo works from any directory
+    let dir = dir.canonicalize()?;
+    let renames = rename::plan(&dir, opts, recursive)?;
 
-    #[test]
-    fn multiple_words() {
-        assert_eq!(super::slugify(&"Hello World"), "hello-world");
+    if dry_run || args.verbose {
+        for r in &renames {
+            println!("{} => {}", r.from.display(), r.to.display());
+        }
     }
-    #[test]
-    fn multiple_words_with_accents() {
-        assert_eq!(super::slugify(&"Così è uno slug!"), "cosi-e-uno-slug");
+    if dry_run || renames.is_empty() {
+        return Ok(());
     }
 
-    #[test]
-    fn test_empty_string() {
-        assert_eq!(super::slugify(&""), "");
-    }
-
-    #[test]
-    fn multiple_spaces() {
-        assert_eq!(super::slugify(&"Hello   World!!"), "hello-world");
-    }
+    // appended to, so the renames of an earlier run can still be undone: undo
+    // reverts them all, the latest first
+    let mut manifest = OpenOptions::new().create(true).append(true).open(manifest)?;
+    rename::execute(&renames, &mut manifest)
+}
 
-    #[test]
-    fn only_special_chars() {
-        assert_eq!(super::slugify(&"!@#$%^&*()"), "-");
-    }
+// once reverted the renames can't be undone again, the manifest goes away
+fn undo(manifest: &Path) -> io::Result<()> {
+    let renames = rename::read_manifest(&std::fs::read_to_string(manifest)?)?;
+    rename::undo(&renames)?;
+    std::fs::remove_file(manifest)
+}
 
-    #[test]
-    fn multiple_spaces_at_the_end() {
-        assert_eq!(super::slugify(&"Hello  World!!  "), "hello-world");
-    }
+fn main() {
+    let args: Args = Args::parse();
 
-    #[test]
-    fn multiple_invalid_chars_at_the_end() {
-        assert_eq!(super::slugify(&"Hello  World/\\"), "hello-world");
-    }
+    let opts = match args.options() {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
 
-    #[test]
-    fn trait_for_string() {
-        let s = String::from("Hello World");
-        assert_eq!(s.to_slug(), "hello-world");
-        assert_eq!(s.is_slug(), false);
-    }
+    let res = match (&args.command, &args.input) {
+        (Some(Command::Rename { dir, dry_run, recursive, manifest }), _) => {
+            rename(&args, &opts, dir, *dry_run, *recursive, manifest)
+        }
+        (Some(Command::Undo { manifest }), _) => undo(manifest),
+        (None, Some(input)) => {
+            let res = args.slugify(input, &opts);
+            if args.verbose {
+                println!("{} => {}", input, res)
+            } else {
+                println!("{}", res)
+            }
+            Ok(())
+        }
+        (None, None) => batch(&args, &opts),
+    };
 
-    #[test]
-    fn trait_for_str() {
-        let s = "Hello World";
-        assert_eq!(s.to_slug(), "hello-world");
-        assert_eq!(s.is_slug(), false);
+    if let Err(e) = res {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
     }
 }
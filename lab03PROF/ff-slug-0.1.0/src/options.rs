@@ -0,0 +1,106 @@
+// options for slugify_with(), built with a chain of methods:
+// SlugOptions::new().separator('_').max_len(20).remove_stop_words()
+
+use crate::custom::CustomTable;
+use crate::locale::Locale;
+
+// common English and Italian words that don't add meaning to a slug
+pub const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "of", "or", "the", "to", "il", "lo", "la", "i", "gli", "le", "un", "una", "di", "e",
+];
+
+#[derive(Debug, Clone)]
+pub struct SlugOptions {
+    pub(crate) separator: char,
+    pub(crate) max_len: Option<usize>,
+    pub(crate) preserve_case: bool,
+    pub(crate) stop_words: Vec<String>,
+    pub(crate) locale: Option<Locale>,
+    pub(crate) custom: Option<CustomTable>,
+    pub(crate) max_depth: Option<usize>,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        SlugOptions {
+            separator: '-',
+            max_len: None,
+            preserve_case: false,
+            stop_words: Vec::new(),
+            locale: None,
+            custom: None,
+            max_depth: None,
+        }
+    }
+}
+
+impl SlugOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    // the slug is truncated at the last word that fits in max_len bytes
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn preserve_case(mut self, preserve_case: bool) -> Self {
+        self.preserve_case = preserve_case;
+        self
+    }
+
+    // words are compared ignoring case, after transliteration
+    pub fn stop_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.stop_words
+            .extend(words.into_iter().map(|w| w.as_ref().to_lowercase()));
+        self
+    }
+
+    pub fn remove_stop_words(self) -> Self {
+        self.stop_words(STOP_WORDS)
+    }
+
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    pub fn custom_table(mut self, table: CustomTable) -> Self {
+        self.custom = Some(table);
+        self
+    }
+
+    // only for slugify_path: how many segments to keep
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    // true if the words of the slug are joined as they are, with '-'
+    pub(crate) fn keeps_words(&self) -> bool {
+        self.separator == '-' && self.max_len.is_none() && self.stop_words.is_empty()
+    }
+
+    // true if a valid slug s would be left unchanged: the locales only change
+    // chars which can't be in a slug, custom keys and stop words could be
+    pub(crate) fn keeps_slugs(&self, s: &str) -> bool {
+        self.separator == '-'
+            && self.max_len.is_none_or(|max| s.len() <= max)
+            && self.stop_words.is_empty()
+            && self.custom.is_none()
+    }
+
+    pub(crate) fn is_stop_word(&self, word: &str) -> bool {
+        self.stop_words.iter().any(|w| w.eq_ignore_ascii_case(word))
+    }
+}
@@ -0,0 +1,83 @@
+// slugs for hierarchical paths like "Prodotti/Caffè & Tè/Macinato Fine":
+// each '/'-separated segment is slugified on its own
+
+use crate::options::SlugOptions;
+use crate::{is_slug, slugify_with};
+
+// empty segments (and segments without letters or digits) are dropped, a leading
+// '/' is kept unless no segment is left (then the path is empty, "/" isn't a valid
+// path); with max_depth only the first segments are kept
+pub fn slugify_path(s: &str, opts: &SlugOptions) -> String {
+    let mut path = String::with_capacity(s.len());
+
+    let segments = s
+        .split('/')
+        .map(|seg| slugify_with(seg, opts))
+        .filter(|seg| !seg.is_empty() && seg.chars().any(|c| c.is_ascii_alphanumeric()))
+        .take(opts.max_depth.unwrap_or(usize::MAX));
+
+    for (i, seg) in segments.enumerate() {
+        if i > 0 || s.starts_with('/') {
+            path.push('/');
+        }
+        path.push_str(&seg);
+    }
+    path
+}
+
+// a valid path is made of valid slugs separated by single '/', with an optional
+// leading '/'
+pub fn is_slug_path(s: &str) -> bool {
+    let s = s.strip_prefix('/').unwrap_or(s);
+    s.split('/').all(is_slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_are_slugified() {
+        let opts = SlugOptions::default();
+        assert_eq!(
+            slugify_path("Prodotti/Caffè & Tè/Macinato Fine", &opts),
+            "prodotti/caffe-te/macinato-fine"
+        );
+        assert_eq!(slugify_path("/Blog/Hello World", &opts), "/blog/hello-world");
+    }
+
+    #[test]
+    fn empty_segments_are_dropped() {
+        let opts = SlugOptions::default();
+        assert_eq!(slugify_path("a//b/ /!!/c/", &opts), "a/b/c");
+        assert_eq!(slugify_path("", &opts), "");
+        // nothing left: not even the leading '/'
+        assert_eq!(slugify_path("/!!", &opts), "");
+        assert_eq!(slugify_path("/", &opts), "");
+    }
+
+    #[test]
+    fn max_depth() {
+        let opts = SlugOptions::new().max_depth(2);
+        assert_eq!(slugify_path("/A/B/C/D", &opts), "/a/b");
+        assert_eq!(slugify_path("A", &opts), "a");
+    }
+
+    #[test]
+    fn options_apply_to_segments() {
+        let opts = SlugOptions::new().separator('_').remove_stop_words();
+        assert_eq!(slugify_path("The Shop/Tea and Coffee", &opts), "shop/tea_coffee");
+    }
+
+    #[test]
+    fn validate_paths() {
+        assert!(is_slug_path("prodotti/caffe-te/macinato-fine"));
+        assert!(is_slug_path("/blog/hello-world"));
+        assert!(is_slug_path("single"));
+        assert!(!is_slug_path(""));
+        assert!(!is_slug_path("/"));
+        assert!(!is_slug_path("a//b"));
+        assert!(!is_slug_path("a/b/"));
+        assert!(!is_slug_path("a/B"));
+    }
+}
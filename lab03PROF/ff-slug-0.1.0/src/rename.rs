@@ -0,0 +1,288 @@
+// rename the entries of a directory to their slugs
+// the work is split in two steps: plan() computes all the renames without touching
+// the file system (that's all a dry run needs), execute() performs them and records
+// each one in an undo manifest, a JSON object per line
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::batch::UniqueSlugs;
+use crate::options::SlugOptions;
+use crate::slugify_with;
+
+#[derive(Debug, PartialEq)]
+pub struct Rename {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+// split "name.ext" in ("name", ".ext"); hidden files and names without a dot
+// have no extension
+fn split_ext(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(i) if i > 0 => name.split_at(i),
+        _ => (name, ""),
+    }
+}
+
+// the renames for the entries of dir, sorted by name so that collisions are always
+// resolved in the same way; with recursive the entries of the subdirectories come
+// first, since renaming a directory changes the path of everything inside it
+pub fn plan(dir: &Path, opts: &SlugOptions, recursive: bool) -> io::Result<Vec<Rename>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        // names which are not valid utf-8 can't be slugified, leave them alone
+        if let Ok(name) = entry.file_name().into_string() {
+            entries.push((name, entry.file_type()?.is_dir()));
+        }
+    }
+    entries.sort();
+
+    let mut renames = Vec::new();
+    let mut slugs = UniqueSlugs::new(opts.separator);
+    let mut todo = Vec::new();
+
+    for (name, is_dir) in entries {
+        if recursive && is_dir {
+            renames.extend(plan(&dir.join(&name), opts, recursive)?);
+        }
+        if name.starts_with('.') {
+            continue;
+        }
+
+        // directories don't have extensions
+        let (stem, ext) = if is_dir { (name.as_str(), "") } else { split_ext(&name) };
+        let slug = slugify_with(stem, opts);
+        if !slug.chars().any(|c| c.is_ascii_alphanumeric()) {
+            // nothing slug-able (e.g. "!!!.txt"), it would become "-.txt": leave it alone
+            continue;
+        }
+        if slug == stem {
+            // already a slug: its name is taken
+            slugs.preload([name]);
+        } else {
+            todo.push((name.clone(), slug.into_owned(), ext.to_string()));
+        }
+    }
+
+    for (name, slug, ext) in todo {
+        let new_name = slugs.unique_with(slug, &ext);
+        renames.push(Rename {
+            from: dir.join(name),
+            to: dir.join(new_name),
+        });
+    }
+    Ok(renames)
+}
+
+// perform the renames, writing each one to the manifest as soon as it's done:
+// if something goes wrong the manifest still describes how to go back
+// the manifest is JSON, so the paths must be valid utf-8: they are all checked
+// before renaming anything, a rename which can't be recorded can't be undone
+pub fn execute<W: Write>(renames: &[Rename], manifest: &mut W) -> io::Result<()> {
+    let mut entries = Vec::with_capacity(renames.len());
+    for r in renames {
+        match (r.from.to_str(), r.to.to_str()) {
+            (Some(from), Some(to)) => entries.push(serde_json::json!({ "from": from, "to": to })),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} is not valid utf-8, it can't go in the manifest", r.from.display()),
+                ))
+            }
+        }
+    }
+
+    for (r, entry) in renames.iter().zip(entries) {
+        if r.to.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", r.to.display()),
+            ));
+        }
+        fs::rename(&r.from, &r.to)?;
+        writeln!(manifest, "{}", entry)?;
+        manifest.flush()?;
+    }
+    Ok(())
+}
+
+// read back a manifest written by execute()
+pub fn read_manifest(contents: &str) -> io::Result<Vec<Rename>> {
+    let mut renames = Vec::new();
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        let v: serde_json::Value = serde_json::from_str(line)?;
+        match (v["from"].as_str(), v["to"].as_str()) {
+            (Some(from), Some(to)) => renames.push(Rename {
+                from: PathBuf::from(from),
+                to: PathBuf::from(to),
+            }),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid manifest entry: {}", line),
+                ))
+            }
+        }
+    }
+    Ok(renames)
+}
+
+// revert the renames in the opposite order; those already reverted (by an undo
+// which stopped half way) are skipped, so undo can simply be run again
+pub fn undo(renames: &[Rename]) -> io::Result<()> {
+    for r in renames.iter().rev() {
+        if r.from.exists() && !r.to.exists() {
+            continue;
+        }
+        fs::rename(&r.to, &r.from)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a fresh directory for each test, removed when dropped
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("ff-slug-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TestDir(dir)
+        }
+
+        fn touch(&self, path: &str) {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        fn names(&self, sub: &str) -> Vec<String> {
+            let mut names: Vec<String> = fs::read_dir(self.0.join(sub))
+                .unwrap()
+                .map(|e| e.unwrap().file_name().into_string().unwrap())
+                .collect();
+            names.sort();
+            names
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn split_extensions() {
+        assert_eq!(split_ext("Photo.JPG"), ("Photo", ".JPG"));
+        assert_eq!(split_ext("archive.tar.gz"), ("archive.tar", ".gz"));
+        assert_eq!(split_ext("README"), ("README", ""));
+        assert_eq!(split_ext(".bashrc"), (".bashrc", ""));
+    }
+
+    #[test]
+    fn plan_keeps_extensions_and_resolves_collisions() {
+        let dir = TestDir::new("plan");
+        dir.touch("My Photo.JPG");
+        dir.touch("my photo!.JPG");
+        dir.touch("my-photo-2.JPG");
+        dir.touch("ok.txt");
+        dir.touch(".hidden file");
+
+        let renames = plan(&dir.0, &SlugOptions::default(), false).unwrap();
+        let names: Vec<_> = renames
+            .iter()
+            .map(|r| (r.from.file_name().unwrap(), r.to.file_name().unwrap()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("My Photo.JPG".as_ref(), "my-photo.JPG".as_ref()),
+                ("my photo!.JPG".as_ref(), "my-photo-3.JPG".as_ref()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_recursive_and_undo() {
+        let dir = TestDir::new("recursive");
+        dir.touch("Sub Dir/Inner File.txt");
+        dir.touch("Top File.txt");
+
+        let renames = plan(&dir.0, &SlugOptions::default(), true).unwrap();
+        assert_eq!(renames.len(), 3);
+
+        let mut manifest = Vec::new();
+        execute(&renames, &mut manifest).unwrap();
+        assert_eq!(dir.names(""), vec!["sub-dir", "top-file.txt"]);
+        assert_eq!(dir.names("sub-dir"), vec!["inner-file.txt"]);
+
+        let renames = read_manifest(std::str::from_utf8(&manifest).unwrap()).unwrap();
+        undo(&renames).unwrap();
+        assert_eq!(dir.names(""), vec!["Sub Dir", "Top File.txt"]);
+        assert_eq!(dir.names("Sub Dir"), vec!["Inner File.txt"]);
+    }
+
+    #[test]
+    fn undo_skips_what_is_already_back() {
+        let dir = TestDir::new("undo-again");
+        dir.touch("First File.txt");
+        dir.touch("Second File.txt");
+
+        let renames = plan(&dir.0, &SlugOptions::default(), false).unwrap();
+        execute(&renames, &mut Vec::new()).unwrap();
+        // an undo which stopped after the last rename
+        fs::rename(&renames[1].to, &renames[1].from).unwrap();
+
+        undo(&renames).unwrap();
+        assert_eq!(dir.names(""), vec!["First File.txt", "Second File.txt"]);
+        undo(&renames).unwrap();
+        assert_eq!(dir.names(""), vec!["First File.txt", "Second File.txt"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn execute_refuses_paths_which_are_not_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = TestDir::new("not-utf8");
+        let sub = dir.0.join(std::ffi::OsStr::from_bytes(b"caf\xe9"));
+        fs::create_dir(&sub).unwrap();
+        fs::write(sub.join("A File"), "").unwrap();
+
+        let renames = plan(&sub, &SlugOptions::default(), false).unwrap();
+        let mut manifest = Vec::new();
+        let err = execute(&renames, &mut manifest).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        // nothing was renamed
+        assert!(manifest.is_empty());
+        assert!(sub.join("A File").exists());
+    }
+
+    #[test]
+    fn plan_skips_names_without_letters_or_digits() {
+        let dir = TestDir::new("unsluggable");
+        dir.touch("!!!.txt");
+        dir.touch("¿¿");
+        dir.touch("Ok File.txt");
+
+        let renames = plan(&dir.0, &SlugOptions::default(), false).unwrap();
+        assert_eq!(renames, vec![Rename { from: dir.0.join("Ok File.txt"), to: dir.0.join("ok-file.txt") }]);
+    }
+
+    #[test]
+    fn plan_without_recursion() {
+        let dir = TestDir::new("flat");
+        dir.touch("Sub Dir/Inner File.txt");
+
+        let renames = plan(&dir.0, &SlugOptions::default(), false).unwrap();
+        assert_eq!(renames, vec![Rename { from: dir.0.join("Sub Dir"), to: dir.0.join("sub-dir") }]);
+    }
+}
@@ -0,0 +1,170 @@
+// a String which is guaranteed to be a valid slug: lowercase ascii letters and digits,
+// in words separated by a single '-'
+// the only way to build it is parsing a &str (or deserializing), which checks it
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// positions are in chars, starting from 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlugError {
+    Empty,
+    Uppercase(usize),
+    IllegalChar(char, usize),
+    LeadingSeparator,
+    TrailingSeparator,
+    DoubleSeparator(usize),
+}
+
+impl Display for SlugError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlugError::Empty => write!(f, "empty slug"),
+            SlugError::Uppercase(i) => write!(f, "uppercase char at position {}", i),
+            SlugError::IllegalChar(c, i) => write!(f, "illegal char {:?} at position {}", c, i),
+            SlugError::LeadingSeparator => write!(f, "leading separator"),
+            SlugError::TrailingSeparator => write!(f, "trailing separator"),
+            SlugError::DoubleSeparator(i) => write!(f, "double separator at position {}", i),
+        }
+    }
+}
+
+impl std::error::Error for SlugError {}
+
+// check s without allocating, reporting the first problem found
+pub fn validate(s: &str) -> Result<(), SlugError> {
+    if s.is_empty() {
+        return Err(SlugError::Empty);
+    }
+    if s.starts_with('-') {
+        return Err(SlugError::LeadingSeparator);
+    }
+
+    let mut prev = ' ';
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            'a'..='z' | '0'..='9' => {}
+            '-' if prev == '-' => return Err(SlugError::DoubleSeparator(i)),
+            '-' => {}
+            _ if c.is_uppercase() => return Err(SlugError::Uppercase(i)),
+            _ => return Err(SlugError::IllegalChar(c, i)),
+        }
+        prev = c;
+    }
+
+    if prev == '-' {
+        return Err(SlugError::TrailingSeparator);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Slug(String);
+
+impl Slug {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for Slug {
+    type Err = SlugError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        validate(s)?;
+        Ok(Slug(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for Slug {
+    type Error = SlugError;
+
+    // no need to copy the string if it's already valid
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        validate(&s)?;
+        Ok(Slug(s))
+    }
+}
+
+impl Display for Slug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for Slug {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Serialize for Slug {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+// deserializing goes through the same validation of parse()
+impl<'de> Deserialize<'de> for Slug {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Slug::try_from(s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_slugs() {
+        for s in ["a", "hello-world", "v1-2-3", "2024"] {
+            assert_eq!(s.parse::<Slug>().unwrap().as_str(), s);
+        }
+    }
+
+    #[test]
+    fn invalid_slugs() {
+        assert_eq!("".parse::<Slug>(), Err(SlugError::Empty));
+        assert_eq!("hello-World".parse::<Slug>(), Err(SlugError::Uppercase(6)));
+        assert_eq!("hello--world".parse::<Slug>(), Err(SlugError::DoubleSeparator(6)));
+        assert_eq!("hello-".parse::<Slug>(), Err(SlugError::TrailingSeparator));
+        assert_eq!("-hello".parse::<Slug>(), Err(SlugError::LeadingSeparator));
+        assert_eq!("così".parse::<Slug>(), Err(SlugError::IllegalChar('ì', 3)));
+        assert_eq!("a_b".parse::<Slug>(), Err(SlugError::IllegalChar('_', 1)));
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(SlugError::Uppercase(6).to_string(), "uppercase char at position 6");
+        assert_eq!(
+            SlugError::IllegalChar('/', 2).to_string(),
+            "illegal char '/' at position 2"
+        );
+    }
+
+    #[test]
+    fn display_and_as_ref() {
+        let s: Slug = "hello-world".parse().unwrap();
+        assert_eq!(format!("{}", s), "hello-world");
+        assert_eq!(s.as_ref(), "hello-world");
+        assert_eq!(Slug::try_from("x-y".to_string()).unwrap().into_string(), "x-y");
+    }
+
+    #[test]
+    fn serde() {
+        let s: Slug = "hello-world".parse().unwrap();
+        assert_eq!(serde_json::to_string(&s).unwrap(), "\"hello-world\"");
+        let s: Slug = serde_json::from_str("\"a-b\"").unwrap();
+        assert_eq!(s.as_str(), "a-b");
+
+        let err = serde_json::from_str::<Slug>("\"a--b\"").unwrap_err();
+        assert!(err.to_string().contains("double separator at position 2"));
+    }
+}
@@ -0,0 +1,351 @@
+// transliteration data for the chars that conv() doesn't know
+// the table maps a lowercase char to its ascii romanisation and is sorted by char,
+// so a lookup is a binary search; CJK ideographs are followed by a space, since
+// each of them is a word on its own
+
+static TABLE: &[(char, &str)] = &[
+    // Latin-1 Supplement, Latin Extended-A and B, IPA
+    ('ª', "a"), ('²', "2"), ('³', "3"), ('¹', "1"), ('º', "o"), ('ð', "d"),
+    ('þ', "th"), ('ĉ', "c"), ('ċ', "c"), ('ĕ', "e"), ('ĝ', "g"), ('ġ', "g"),
+    ('ģ', "g"), ('ĥ', "h"), ('ħ', "h"), ('ĩ', "i"), ('ĭ', "i"), ('ĳ', "ij"),
+    ('ĵ', "j"), ('ķ', "k"), ('ĸ', "q"), ('ĺ', "l"), ('ļ', "l"), ('ľ', "l"),
+    ('ŀ', "l"), ('ņ', "n"), ('ŉ', "n"), ('ŋ', "ng"), ('ŏ', "o"), ('ŗ', "r"),
+    ('ŝ', "s"), ('ţ', "t"), ('ũ', "u"), ('ŭ', "u"), ('ŵ', "w"), ('ŷ', "y"),
+    ('ſ', "s"), ('ƀ', "b"), ('ƃ', "b"), ('ƈ', "c"), ('ƌ', "d"), ('ƒ', "f"),
+    ('ƕ', "hv"), ('ƙ', "k"), ('ƚ', "l"), ('ƞ', "n"), ('ơ', "o"), ('ƣ', "oi"),
+    ('ƥ', "p"), ('ƭ', "t"), ('ư', "u"), ('ƴ', "y"), ('ƶ', "z"), ('ǆ', "dz"),
+    ('ǉ', "lj"), ('ǌ', "nj"), ('ǎ', "a"), ('ǐ', "i"), ('ǒ', "o"), ('ǔ', "u"),
+    ('ǖ', "u"), ('ǚ', "u"), ('ǜ', "u"), ('ǝ', "e"), ('ǟ', "a"), ('ǡ', "a"),
+    ('ǥ', "g"), ('ǧ', "g"), ('ǩ', "k"), ('ǫ', "o"), ('ǭ', "o"), ('ǰ', "j"),
+    ('ǳ', "dz"), ('ǻ', "a"), ('ȁ', "a"), ('ȃ', "a"), ('ȅ', "e"), ('ȇ', "e"),
+    ('ȉ', "i"), ('ȋ', "i"), ('ȍ', "o"), ('ȏ', "o"), ('ȑ', "r"), ('ȓ', "r"),
+    ('ȕ', "u"), ('ȗ', "u"), ('ȟ', "h"), ('ȣ', "ou"), ('ȥ', "z"), ('ȧ', "a"),
+    ('ȩ', "e"), ('ȫ', "o"), ('ȭ', "o"), ('ȯ', "o"), ('ȱ', "o"), ('ȳ', "y"),
+    ('ȼ', "c"), ('ȿ', "s"), ('ɀ', "z"), ('ɇ', "e"), ('ɉ', "j"), ('ɍ', "r"),
+    ('ɏ', "y"), ('ɓ', "b"), ('ɔ', "o"), ('ɗ', "d"), ('ə', "e"), ('ɛ', "e"),
+    ('ɠ', "g"), ('ɨ', "i"), ('ɲ', "n"), ('ʉ', "u"), ('ʒ', "z"),
+    // Greek
+    ('ΐ', "i"), ('ά', "a"), ('έ', "e"), ('ή', "i"), ('ί', "i"), ('ΰ', "y"),
+    ('α', "a"), ('β', "v"), ('γ', "g"), ('δ', "d"), ('ε', "e"), ('ζ', "z"),
+    ('η', "i"), ('θ', "th"), ('ι', "i"), ('κ', "k"), ('λ', "l"), ('μ', "m"),
+    ('ν', "n"), ('ξ', "x"), ('ο', "o"), ('π', "p"), ('ρ', "r"), ('ς', "s"),
+    ('σ', "s"), ('τ', "t"), ('υ', "y"), ('φ', "f"), ('χ', "ch"), ('ψ', "ps"),
+    ('ω', "o"), ('ϊ', "i"), ('ϋ', "y"), ('ό', "o"), ('ύ', "y"), ('ώ', "o"),
+    ('ϐ', "v"), ('ϑ', "th"), ('ϒ', "y"), ('ϓ', "y"), ('ϔ', "y"), ('ϕ', "f"),
+    ('ϖ', "p"), ('ϰ', "k"), ('ϱ', "r"), ('ϲ', "s"), ('ϵ', "e"),
+    // Cyrillic
+    ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"), ('е', "e"),
+    ('ж', "zh"), ('з', "z"), ('и', "i"), ('й', "y"), ('к', "k"), ('л', "l"),
+    ('м', "m"), ('н', "n"), ('о', "o"), ('п', "p"), ('р', "r"), ('с', "s"),
+    ('т', "t"), ('у', "u"), ('ф', "f"), ('х', "kh"), ('ц', "ts"), ('ч', "ch"),
+    ('ш', "sh"), ('щ', "shch"), ('ъ', ""), ('ы', "y"), ('ь', ""), ('э', "e"),
+    ('ю', "yu"), ('я', "ya"), ('ѐ', "e"), ('ё', "e"), ('ђ', "dj"), ('ѓ', "gj"),
+    ('є', "ye"), ('ѕ', "dz"), ('і', "i"), ('ї', "yi"), ('ј', "j"), ('љ', "lj"),
+    ('њ', "nj"), ('ћ', "c"), ('ќ', "kj"), ('ѝ', "i"), ('ў', "u"), ('џ', "dz"),
+    ('ґ', "g"), ('ғ', "gh"), ('қ', "q"), ('ң', "ng"), ('ү', "u"), ('ұ', "u"),
+    ('һ', "h"), ('ӂ', "zh"), ('ӑ', "a"), ('ӓ', "a"), ('ӗ', "e"), ('ә', "a"),
+    ('ӛ', "a"), ('ӝ', "zh"), ('ӟ', "z"), ('ӣ', "i"), ('ӥ', "i"), ('ӧ', "o"),
+    ('ө', "o"), ('ӫ', "o"), ('ӭ', "e"), ('ӯ', "u"), ('ӱ', "u"), ('ӳ', "u"),
+    ('ӵ', "ch"), ('ӹ', "y"),
+    // Hebrew
+    ('א', "a"), ('ב', "b"), ('ג', "g"), ('ד', "d"), ('ה', "h"), ('ו', "v"),
+    ('ז', "z"), ('ח', "kh"), ('ט', "t"), ('י', "y"), ('ך', "k"), ('כ', "k"),
+    ('ל', "l"), ('ם', "m"), ('מ', "m"), ('ן', "n"), ('נ', "n"), ('ס', "s"),
+    ('ע', ""), ('ף', "p"), ('פ', "p"), ('ץ', "ts"), ('צ', "ts"), ('ק', "k"),
+    ('ר', "r"), ('ש', "sh"), ('ת', "t"),
+    // Arabic and Persian
+    ('ء', ""), ('آ', "a"), ('أ', "a"), ('ؤ', "w"), ('إ', "i"), ('ئ', "y"),
+    ('ا', "a"), ('ب', "b"), ('ة', "h"), ('ت', "t"), ('ث', "th"), ('ج', "j"),
+    ('ح', "h"), ('خ', "kh"), ('د', "d"), ('ذ', "dh"), ('ر', "r"), ('ز', "z"),
+    ('س', "s"), ('ش', "sh"), ('ص', "s"), ('ض', "d"), ('ط', "t"), ('ظ', "z"),
+    ('ع', ""), ('غ', "gh"), ('ف', "f"), ('ق', "q"), ('ك', "k"), ('ل', "l"),
+    ('م', "m"), ('ن', "n"), ('ه', "h"), ('و', "w"), ('ى', "a"), ('ي', "y"),
+    ('٠', "0"), ('١', "1"), ('٢', "2"), ('٣', "3"), ('٤', "4"), ('٥', "5"),
+    ('٦', "6"), ('٧', "7"), ('٨', "8"), ('٩', "9"), ('ٱ', "a"), ('پ', "p"),
+    ('چ', "ch"), ('ژ', "zh"), ('ک', "k"), ('گ', "g"), ('ی', "y"), ('۰', "0"),
+    ('۱', "1"), ('۲', "2"), ('۳', "3"), ('۴', "4"), ('۵', "5"), ('۶', "6"),
+    ('۷', "7"), ('۸', "8"), ('۹', "9"),
+    // Latin Extended Additional (Vietnamese)
+    ('ḁ', "a"), ('ḃ', "b"), ('ḅ', "b"), ('ḇ', "b"), ('ḉ', "c"), ('ḋ', "d"),
+    ('ḍ', "d"), ('ḏ', "d"), ('ḑ', "d"), ('ḓ', "d"), ('ḕ', "e"), ('ḗ', "e"),
+    ('ḙ', "e"), ('ḛ', "e"), ('ḝ', "e"), ('ḟ', "f"), ('ḡ', "g"), ('ḣ', "h"),
+    ('ḥ', "h"), ('ḩ', "h"), ('ḫ', "h"), ('ḭ', "i"), ('ḯ', "i"), ('ḱ', "k"),
+    ('ḳ', "k"), ('ḵ', "k"), ('ḷ', "l"), ('ḹ', "l"), ('ḻ', "l"), ('ḽ', "l"),
+    ('ṁ', "m"), ('ṃ', "m"), ('ṅ', "n"), ('ṇ', "n"), ('ṉ', "n"), ('ṋ', "n"),
+    ('ṍ', "o"), ('ṏ', "o"), ('ṑ', "o"), ('ṓ', "o"), ('ṗ', "p"), ('ṙ', "r"),
+    ('ṛ', "r"), ('ṝ', "r"), ('ṟ', "r"), ('ṡ', "s"), ('ṣ', "s"), ('ṥ', "s"),
+    ('ṧ', "s"), ('ṩ', "s"), ('ṫ', "t"), ('ṭ', "t"), ('ṯ', "t"), ('ṱ', "t"),
+    ('ṳ', "u"), ('ṵ', "u"), ('ṷ', "u"), ('ṹ', "u"), ('ṻ', "u"), ('ṽ', "v"),
+    ('ṿ', "v"), ('ẁ', "w"), ('ẅ', "w"), ('ẇ', "w"), ('ẉ', "w"), ('ẋ', "x"),
+    ('ẏ', "y"), ('ẑ', "z"), ('ẓ', "z"), ('ẕ', "z"), ('ẖ', "h"), ('ẗ', "t"),
+    ('ẘ', "w"), ('ẙ', "y"), ('ẛ', "s"), ('ạ', "a"), ('ả', "a"), ('ấ', "a"),
+    ('ầ', "a"), ('ẩ', "a"), ('ẫ', "a"), ('ậ', "a"), ('ắ', "a"), ('ằ', "a"),
+    ('ẳ', "a"), ('ẵ', "a"), ('ặ', "a"), ('ẹ', "e"), ('ẻ', "e"), ('ẽ', "e"),
+    ('ế', "e"), ('ề', "e"), ('ể', "e"), ('ễ', "e"), ('ệ', "e"), ('ỉ', "i"),
+    ('ị', "i"), ('ọ', "o"), ('ỏ', "o"), ('ố', "o"), ('ồ', "o"), ('ổ', "o"),
+    ('ỗ', "o"), ('ộ', "o"), ('ớ', "o"), ('ờ', "o"), ('ở', "o"), ('ỡ', "o"),
+    ('ợ', "o"), ('ụ', "u"), ('ủ', "u"), ('ứ', "u"), ('ừ', "u"), ('ử', "u"),
+    ('ữ', "u"), ('ự', "u"), ('ỳ', "y"), ('ỵ', "y"), ('ỷ', "y"), ('ỹ', "y"),
+    // Greek Extended (polytonic)
+    ('ἀ', "a"), ('ἁ', "a"), ('ἂ', "a"), ('ἃ', "a"), ('ἄ', "a"), ('ἅ', "a"),
+    ('ἆ', "a"), ('ἇ', "a"), ('ἐ', "e"), ('ἑ', "e"), ('ἒ', "e"), ('ἓ', "e"),
+    ('ἔ', "e"), ('ἕ', "e"), ('ἠ', "i"), ('ἡ', "i"), ('ἢ', "i"), ('ἣ', "i"),
+    ('ἤ', "i"), ('ἥ', "i"), ('ἦ', "i"), ('ἧ', "i"), ('ἰ', "i"), ('ἱ', "i"),
+    ('ἲ', "i"), ('ἳ', "i"), ('ἴ', "i"), ('ἵ', "i"), ('ἶ', "i"), ('ἷ', "i"),
+    ('ὀ', "o"), ('ὁ', "o"), ('ὂ', "o"), ('ὃ', "o"), ('ὄ', "o"), ('ὅ', "o"),
+    ('ὐ', "y"), ('ὑ', "y"), ('ὒ', "y"), ('ὓ', "y"), ('ὔ', "y"), ('ὕ', "y"),
+    ('ὖ', "y"), ('ὗ', "y"), ('ὠ', "o"), ('ὡ', "o"), ('ὢ', "o"), ('ὣ', "o"),
+    ('ὤ', "o"), ('ὥ', "o"), ('ὦ', "o"), ('ὧ', "o"), ('ὰ', "a"), ('ά', "a"),
+    ('ὲ', "e"), ('έ', "e"), ('ὴ', "i"), ('ή', "i"), ('ὶ', "i"), ('ί', "i"),
+    ('ὸ', "o"), ('ό', "o"), ('ὺ', "y"), ('ύ', "y"), ('ὼ', "o"), ('ώ', "o"),
+    ('ᾀ', "a"), ('ᾁ', "a"), ('ᾂ', "a"), ('ᾃ', "a"), ('ᾄ', "a"), ('ᾅ', "a"),
+    ('ᾆ', "a"), ('ᾇ', "a"), ('ᾐ', "i"), ('ᾑ', "i"), ('ᾒ', "i"), ('ᾓ', "i"),
+    ('ᾔ', "i"), ('ᾕ', "i"), ('ᾖ', "i"), ('ᾗ', "i"), ('ᾠ', "o"), ('ᾡ', "o"),
+    ('ᾢ', "o"), ('ᾣ', "o"), ('ᾤ', "o"), ('ᾥ', "o"), ('ᾦ', "o"), ('ᾧ', "o"),
+    ('ᾰ', "a"), ('ᾱ', "a"), ('ᾲ', "a"), ('ᾳ', "a"), ('ᾴ', "a"), ('ᾶ', "a"),
+    ('ᾷ', "a"), ('ι', "i"), ('ῂ', "i"), ('ῃ', "i"), ('ῄ', "i"), ('ῆ', "i"),
+    ('ῇ', "i"), ('ῐ', "i"), ('ῑ', "i"), ('ῒ', "i"), ('ΐ', "i"), ('ῖ', "i"),
+    ('ῗ', "i"), ('ῠ', "y"), ('ῡ', "y"), ('ῢ', "y"), ('ΰ', "y"), ('ῤ', "r"),
+    ('ῥ', "r"), ('ῦ', "y"), ('ῧ', "y"), ('ῲ', "o"), ('ῳ', "o"), ('ῴ', "o"),
+    ('ῶ', "o"), ('ῷ', "o"),
+    // Latin Extended-C
+    ('ⱥ', "a"), ('ⱦ', "t"), ('ⱼ', "j"), ('ⱽ', "v"),
+    // Hiragana and Katakana (Hepburn)
+    ('ぁ', "a"), ('あ', "a"), ('ぃ', "i"), ('い', "i"), ('ぅ', "u"), ('う', "u"),
+    ('ぇ', "e"), ('え', "e"), ('ぉ', "o"), ('お', "o"), ('か', "ka"), ('が', "ga"),
+    ('き', "ki"), ('ぎ', "gi"), ('く', "ku"), ('ぐ', "gu"), ('け', "ke"), ('げ', "ge"),
+    ('こ', "ko"), ('ご', "go"), ('さ', "sa"), ('ざ', "za"), ('し', "shi"), ('じ', "ji"),
+    ('す', "su"), ('ず', "zu"), ('せ', "se"), ('ぜ', "ze"), ('そ', "so"), ('ぞ', "zo"),
+    ('た', "ta"), ('だ', "da"), ('ち', "chi"), ('ぢ', "ji"), ('っ', ""), ('つ', "tsu"),
+    ('づ', "zu"), ('て', "te"), ('で', "de"), ('と', "to"), ('ど', "do"), ('な', "na"),
+    ('に', "ni"), ('ぬ', "nu"), ('ね', "ne"), ('の', "no"), ('は', "ha"), ('ば', "ba"),
+    ('ぱ', "pa"), ('ひ', "hi"), ('び', "bi"), ('ぴ', "pi"), ('ふ', "fu"), ('ぶ', "bu"),
+    ('ぷ', "pu"), ('へ', "he"), ('べ', "be"), ('ぺ', "pe"), ('ほ', "ho"), ('ぼ', "bo"),
+    ('ぽ', "po"), ('ま', "ma"), ('み', "mi"), ('む', "mu"), ('め', "me"), ('も', "mo"),
+    ('ゃ', "ya"), ('や', "ya"), ('ゅ', "yu"), ('ゆ', "yu"), ('ょ', "yo"), ('よ', "yo"),
+    ('ら', "ra"), ('り', "ri"), ('る', "ru"), ('れ', "re"), ('ろ', "ro"), ('ゎ', "wa"),
+    ('わ', "wa"), ('ゐ', "wi"), ('ゑ', "we"), ('を', "wo"), ('ん', "n"), ('ゔ', "vu"),
+    ('ゕ', "ka"), ('ゖ', "ke"), ('ァ', "a"), ('ア', "a"), ('ィ', "i"), ('イ', "i"),
+    ('ゥ', "u"), ('ウ', "u"), ('ェ', "e"), ('エ', "e"), ('ォ', "o"), ('オ', "o"),
+    ('カ', "ka"), ('ガ', "ga"), ('キ', "ki"), ('ギ', "gi"), ('ク', "ku"), ('グ', "gu"),
+    ('ケ', "ke"), ('ゲ', "ge"), ('コ', "ko"), ('ゴ', "go"), ('サ', "sa"), ('ザ', "za"),
+    ('シ', "shi"), ('ジ', "ji"), ('ス', "su"), ('ズ', "zu"), ('セ', "se"), ('ゼ', "ze"),
+    ('ソ', "so"), ('ゾ', "zo"), ('タ', "ta"), ('ダ', "da"), ('チ', "chi"), ('ヂ', "ji"),
+    ('ッ', ""), ('ツ', "tsu"), ('ヅ', "zu"), ('テ', "te"), ('デ', "de"), ('ト', "to"),
+    ('ド', "do"), ('ナ', "na"), ('ニ', "ni"), ('ヌ', "nu"), ('ネ', "ne"), ('ノ', "no"),
+    ('ハ', "ha"), ('バ', "ba"), ('パ', "pa"), ('ヒ', "hi"), ('ビ', "bi"), ('ピ', "pi"),
+    ('フ', "fu"), ('ブ', "bu"), ('プ', "pu"), ('ヘ', "he"), ('ベ', "be"), ('ペ', "pe"),
+    ('ホ', "ho"), ('ボ', "bo"), ('ポ', "po"), ('マ', "ma"), ('ミ', "mi"), ('ム', "mu"),
+    ('メ', "me"), ('モ', "mo"), ('ャ', "ya"), ('ヤ', "ya"), ('ュ', "yu"), ('ユ', "yu"),
+    ('ョ', "yo"), ('ヨ', "yo"), ('ラ', "ra"), ('リ', "ri"), ('ル', "ru"), ('レ', "re"),
+    ('ロ', "ro"), ('ヮ', "wa"), ('ワ', "wa"), ('ヰ', "wi"), ('ヱ', "we"), ('ヲ', "wo"),
+    ('ン', "n"), ('ヴ', "vu"), ('ヵ', "ka"), ('ヶ', "ke"), ('ヷ', "va"), ('ヸ', "vi"),
+    ('ヹ', "ve"), ('ヺ', "vo"), ('ー', ""),
+    // common CJK ideographs (pinyin)
+    ('一', "yi "), ('七', "qi "), ('万', "wan "), ('三', "san "), ('上', "shang "), ('下', "xia "),
+    ('不', "bu "), ('与', "yu "), ('世', "shi "), ('业', "ye "), ('东', "dong "), ('两', "liang "),
+    ('个', "ge "), ('中', "zhong "), ('为', "wei "), ('主', "zhu "), ('么', "me "), ('义', "yi "),
+    ('之', "zhi "), ('乐', "le "), ('九', "jiu "), ('也', "ye "), ('书', "shu "), ('买', "mai "),
+    ('了', "le "), ('事', "shi "), ('二', "er "), ('于', "yu "), ('云', "yun "), ('五', "wu "),
+    ('些', "xie "), ('交', "jiao "), ('产', "chan "), ('京', "jing "), ('亲', "qin "), ('人', "ren "),
+    ('什', "shen "), ('今', "jin "), ('从', "cong "), ('他', "ta "), ('代', "dai "), ('以', "yi "),
+    ('们', "men "), ('件', "jian "), ('价', "jia "), ('任', "ren "), ('众', "zhong "), ('会', "hui "),
+    ('传', "chuan "), ('但', "dan "), ('位', "wei "), ('住', "zhu "), ('体', "ti "), ('何', "he "),
+    ('作', "zuo "), ('你', "ni "), ('使', "shi "), ('便', "bian "), ('保', "bao "), ('信', "xin "),
+    ('候', "hou "), ('做', "zuo "), ('儿', "er "), ('元', "yuan "), ('先', "xian "), ('光', "guang "),
+    ('入', "ru "), ('全', "quan "), ('八', "ba "), ('公', "gong "), ('六', "liu "), ('共', "gong "),
+    ('关', "guan "), ('其', "qi "), ('内', "nei "), ('再', "zai "), ('军', "jun "), ('冬', "dong "),
+    ('决', "jue "), ('几', "ji "), ('出', "chu "), ('分', "fen "), ('利', "li "), ('别', "bie "),
+    ('到', "dao "), ('制', "zhi "), ('前', "qian "), ('力', "li "), ('办', "ban "), ('功', "gong "),
+    ('加', "jia "), ('务', "wu "), ('动', "dong "), ('化', "hua "), ('北', "bei "), ('区', "qu "),
+    ('医', "yi "), ('十', "shi "), ('千', "qian "), ('午', "wu "), ('半', "ban "), ('华', "hua "),
+    ('卖', "mai "), ('南', "nan "), ('即', "ji "), ('却', "que "), ('原', "yuan "), ('去', "qu "),
+    ('县', "xian "), ('又', "you "), ('及', "ji "), ('友', "you "), ('反', "fan "), ('发', "fa "),
+    ('受', "shou "), ('变', "bian "), ('口', "kou "), ('只', "zhi "), ('叫', "jiao "), ('可', "ke "),
+    ('台', "tai "), ('右', "you "), ('号', "hao "), ('司', "si "), ('吃', "chi "), ('各', "ge "),
+    ('合', "he "), ('同', "tong "), ('名', "ming "), ('后', "hou "), ('向', "xiang "), ('听', "ting "),
+    ('员', "yuan "), ('周', "zhou "), ('命', "ming "), ('和', "he "), ('品', "pin "), ('哥', "ge "),
+    ('商', "shang "), ('四', "si "), ('回', "hui "), ('因', "yin "), ('国', "guo "), ('图', "tu "),
+    ('土', "tu "), ('在', "zai "), ('地', "di "), ('圳', "zhen "), ('场', "chang "), ('城', "cheng "),
+    ('士', "shi "), ('声', "sheng "), ('处', "chu "), ('夏', "xia "), ('外', "wai "), ('多', "duo "),
+    ('大', "da "), ('天', "tian "), ('太', "tai "), ('夫', "fu "), ('头', "tou "), ('女', "nu "),
+    ('她', "ta "), ('好', "hao "), ('如', "ru "), ('妈', "ma "), ('妹', "mei "), ('姐', "jie "),
+    ('子', "zi "), ('字', "zi "), ('学', "xue "), ('孩', "hai "), ('它', "ta "), ('安', "an "),
+    ('完', "wan "), ('定', "ding "), ('实', "shi "), ('家', "jia "), ('对', "dui "), ('将', "jiang "),
+    ('小', "xiao "), ('少', "shao "), ('就', "jiu "), ('展', "zhan "), ('山', "shan "), ('州', "zhou "),
+    ('工', "gong "), ('左', "zuo "), ('己', "ji "), ('已', "yi "), ('市', "shi "), ('师', "shi "),
+    ('带', "dai "), ('常', "chang "), ('干', "gan "), ('平', "ping "), ('年', "nian "), ('并', "bing "),
+    ('广', "guang "), ('应', "ying "), ('店', "dian "), ('度', "du "), ('建', "jian "), ('开', "kai "),
+    ('弟', "di "), ('张', "zhang "), ('强', "qiang "), ('当', "dang "), ('往', "wang "), ('很', "hen "),
+    ('得', "de "), ('德', "de "), ('心', "xin "), ('必', "bi "), ('快', "kuai "), ('怎', "zen "),
+    ('思', "si "), ('性', "xing "), ('总', "zong "), ('情', "qing "), ('想', "xiang "), ('意', "yi "),
+    ('感', "gan "), ('戏', "xi "), ('成', "cheng "), ('我', "wo "), ('或', "huo "), ('战', "zhan "),
+    ('房', "fang "), ('所', "suo "), ('手', "shou "), ('才', "cai "), ('打', "da "), ('找', "zhao "),
+    ('技', "ji "), ('把', "ba "), ('报', "bao "), ('指', "zhi "), ('接', "jie "), ('提', "ti "),
+    ('放', "fang "), ('政', "zheng "), ('教', "jiao "), ('数', "shu "), ('文', "wen "), ('新', "xin "),
+    ('方', "fang "), ('无', "wu "), ('日', "ri "), ('早', "zao "), ('时', "shi "), ('明', "ming "),
+    ('星', "xing "), ('春', "chun "), ('是', "shi "), ('晚', "wan "), ('更', "geng "), ('最', "zui "),
+    ('月', "yue "), ('有', "you "), ('朋', "peng "), ('服', "fu "), ('望', "wang "), ('期', "qi "),
+    ('木', "mu "), ('本', "ben "), ('术', "shu "), ('机', "ji "), ('权', "quan "), ('村', "cun "),
+    ('条', "tiao "), ('来', "lai "), ('杭', "hang "), ('林', "lin "), ('果', "guo "), ('样', "yang "),
+    ('格', "ge "), ('桥', "qiao "), ('楼', "lou "), ('次', "ci "), ('正', "zheng "), ('此', "ci "),
+    ('步', "bu "), ('武', "wu "), ('死', "si "), ('每', "mei "), ('比', "bi "), ('毛', "mao "),
+    ('民', "min "), ('气', "qi "), ('水', "shui "), ('求', "qiu "), ('汉', "han "), ('汤', "tang "),
+    ('没', "mei "), ('河', "he "), ('治', "zhi "), ('法', "fa "), ('活', "huo "), ('流', "liu "),
+    ('海', "hai "), ('深', "shen "), ('清', "qing "), ('港', "gang "), ('游', "you "), ('湾', "wan "),
+    ('澳', "ao "), ('火', "huo "), ('点', "dian "), ('然', "ran "), ('爱', "ai "), ('父', "fu "),
+    ('爸', "ba "), ('片', "pian "), ('牛', "niu "), ('物', "wu "), ('特', "te "), ('狗', "gou "),
+    ('猪', "zhu "), ('猫', "mao "), ('王', "wang "), ('现', "xian "), ('理', "li "), ('甚', "shen "),
+    ('生', "sheng "), ('用', "yong "), ('田', "tian "), ('由', "you "), ('电', "dian "), ('男', "nan "),
+    ('界', "jie "), ('留', "liu "), ('病', "bing "), ('白', "bai "), ('百', "bai "), ('的', "de "),
+    ('目', "mu "), ('直', "zhi "), ('相', "xiang "), ('省', "sheng "), ('看', "kan "), ('真', "zhen "),
+    ('眼', "yan "), ('着', "zhe "), ('知', "zhi "), ('石', "shi "), ('社', "she "), ('神', "shen "),
+    ('秋', "qiu "), ('种', "zhong "), ('科', "ke "), ('秒', "miao "), ('程', "cheng "), ('空', "kong "),
+    ('立', "li "), ('站', "zhan "), ('笑', "xiao "), ('第', "di "), ('等', "deng "), ('算', "suan "),
+    ('管', "guan "), ('米', "mi "), ('系', "xi "), ('紫', "zi "), ('红', "hong "), ('经', "jing "),
+    ('结', "jie "), ('给', "gei "), ('绝', "jue "), ('统', "tong "), ('绿', "lu "), ('网', "wang "),
+    ('羊', "yang "), ('美', "mei "), ('老', "lao "), ('者', "zhe "), ('而', "er "), ('联', "lian "),
+    ('肉', "rou "), ('能', "neng "), ('自', "zi "), ('至', "zhi "), ('色', "se "), ('花', "hua "),
+    ('英', "ying "), ('茶', "cha "), ('药', "yao "), ('菜', "cai "), ('蓝', "lan "), ('行', "xing "),
+    ('街', "jie "), ('表', "biao "), ('被', "bei "), ('西', "xi "), ('要', "yao "), ('见', "jian "),
+    ('视', "shi "), ('觉', "jue "), ('解', "jie "), ('言', "yan "), ('计', "ji "), ('认', "ren "),
+    ('让', "rang "), ('记', "ji "), ('许', "xu "), ('论', "lun "), ('话', "hua "), ('该', "gai "),
+    ('语', "yu "), ('说', "shuo "), ('请', "qing "), ('象', "xiang "), ('资', "zi "), ('走', "zou "),
+    ('起', "qi "), ('跟', "gen "), ('路', "lu "), ('身', "shen "), ('车', "che "), ('转', "zhuan "),
+    ('轻', "qing "), ('边', "bian "), ('达', "da "), ('过', "guo "), ('运', "yun "), ('近', "jin "),
+    ('还', "hai "), ('这', "zhe "), ('进', "jin "), ('远', "yuan "), ('连', "lian "), ('通', "tong "),
+    ('道', "dao "), ('那', "na "), ('部', "bu "), ('都', "dou "), ('酒', "jiu "), ('里', "li "),
+    ('重', "zhong "), ('量', "liang "), ('金', "jin "), ('钟', "zhong "), ('钱', "qian "), ('银', "yin "),
+    ('长', "chang "), ('门', "men "), ('问', "wen "), ('间', "jian "), ('队', "dui "), ('院', "yuan "),
+    ('难', "nan "), ('雨', "yu "), ('雪', "xue "), ('零', "ling "), ('青', "qing "), ('非', "fei "),
+    ('面', "mian "), ('革', "ge "), ('韩', "han "), ('音', "yin "), ('页', "ye "), ('领', "ling "),
+    ('频', "pin "), ('题', "ti "), ('风', "feng "), ('饭', "fan "), ('首', "shou "), ('香', "xiang "),
+    ('马', "ma "), ('高', "gao "), ('鱼', "yu "), ('鸟', "niao "), ('黄', "huang "), ('黑', "hei "),
+    ('龙', "long "),
+    // Latin ligatures
+    ('ﬀ', "ff"), ('ﬁ', "fi"), ('ﬂ', "fl"), ('ﬃ', "ffi"), ('ﬄ', "ffl"), ('ﬅ', "st"),
+    ('ﬆ', "st"),
+    // fullwidth digits and letters
+    ('０', "0"), ('１', "1"), ('２', "2"), ('３', "3"), ('４', "4"), ('５', "5"),
+    ('６', "6"), ('７', "7"), ('８', "8"), ('９', "9"), ('ａ', "a"), ('ｂ', "b"),
+    ('ｃ', "c"), ('ｄ', "d"), ('ｅ', "e"), ('ｆ', "f"), ('ｇ', "g"), ('ｈ', "h"),
+    ('ｉ', "i"), ('ｊ', "j"), ('ｋ', "k"), ('ｌ', "l"), ('ｍ', "m"), ('ｎ', "n"),
+    ('ｏ', "o"), ('ｐ', "p"), ('ｑ', "q"), ('ｒ', "r"), ('ｓ', "s"), ('ｔ', "t"),
+    ('ｕ', "u"), ('ｖ', "v"), ('ｗ', "w"), ('ｘ', "x"), ('ｙ', "y"), ('ｚ', "z"),
+];
+
+// combining marks (accents written as a separate char, Hebrew points, Arabic harakat)
+// carry no letter and are simply dropped
+const MARKS: &[(char, char)] = &[
+    ('\u{300}', '\u{36f}'),
+    ('\u{591}', '\u{5bd}'),
+    ('\u{5bf}', '\u{5bf}'),
+    ('\u{5c1}', '\u{5c2}'),
+    ('\u{5c4}', '\u{5c5}'),
+    ('\u{5c7}', '\u{5c7}'),
+    ('\u{610}', '\u{61a}'),
+    ('\u{64b}', '\u{65f}'),
+    ('\u{670}', '\u{670}'),
+    ('\u{6d6}', '\u{6dc}'),
+    ('\u{6df}', '\u{6e4}'),
+    ('\u{6e7}', '\u{6e8}'),
+    ('\u{6ea}', '\u{6ed}'),
+];
+
+// Hangul syllables are not listed, they are composed by an initial, a vowel and an
+// optional final jamo and can be romanised computing the three indexes
+// (Revised Romanization of Korean)
+const HANGUL_FIRST: u32 = 0xAC00;
+const HANGUL_LAST: u32 = 0xD7A3;
+
+const INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p", "h",
+];
+const VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we", "wi", "yu", "eu",
+    "ui", "i",
+];
+const FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p", "t", "t", "ng",
+    "t", "t", "k", "t", "p", "t",
+];
+
+// calls push for each char of the romanisation of c
+// returns false if c is unknown, and the caller has to decide what to do with it
+pub fn translit<F: FnMut(char)>(c: char, mut push: F) -> bool {
+    if let Ok(i) = TABLE.binary_search_by_key(&c, |&(k, _)| k) {
+        TABLE[i].1.chars().for_each(push);
+        return true;
+    }
+
+    if MARKS.iter().any(|&(first, last)| first <= c && c <= last) {
+        return true;
+    }
+
+    let code = c as u32;
+    if (HANGUL_FIRST..=HANGUL_LAST).contains(&code) {
+        let s = (code - HANGUL_FIRST) as usize;
+        let (l, v, t) = (s / (21 * 28), s % (21 * 28) / 28, s % 28);
+        for part in [INITIALS[l], VOWELS[v], FINALS[t]] {
+            part.chars().for_each(&mut push);
+        }
+        return true;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_string(c: char) -> Option<String> {
+        let mut s = String::new();
+        if translit(c, |x| s.push(x)) {
+            Some(s)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn table_is_sorted() {
+        assert!(TABLE.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn table_is_ascii() {
+        for (_, s) in TABLE {
+            assert!(s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == ' '));
+        }
+    }
+
+    #[test]
+    fn known_chars() {
+        assert_eq!(to_string('ж').as_deref(), Some("zh"));
+        assert_eq!(to_string('ῶ').as_deref(), Some("o"));
+        assert_eq!(to_string('ệ').as_deref(), Some("e"));
+        assert_eq!(to_string('ש').as_deref(), Some("sh"));
+        assert_eq!(to_string('ش').as_deref(), Some("sh"));
+        assert_eq!(to_string('ﬁ').as_deref(), Some("fi"));
+        assert_eq!(to_string('中').as_deref(), Some("zhong "));
+    }
+
+    #[test]
+    fn marks_are_dropped() {
+        assert_eq!(to_string('\u{301}').as_deref(), Some(""));
+        assert_eq!(to_string('\u{64e}').as_deref(), Some(""));
+    }
+
+    #[test]
+    fn hangul() {
+        assert_eq!(to_string('한').as_deref(), Some("han"));
+        assert_eq!(to_string('글').as_deref(), Some("geul"));
+        assert_eq!(to_string('서').as_deref(), Some("seo"));
+    }
+
+    #[test]
+    fn unknown_char() {
+        assert_eq!(to_string('☃'), None);
+    }
+}